@@ -0,0 +1,46 @@
+#!/usr/bin/env bash
+# Build the whisper.cpp command-line tool and place it where Tauri bundles sidecars:
+#   src-tauri/binaries/whisper-cli-<target-triple>[.exe]
+#
+# Usage: scripts/build-whisper-cli.sh [target-triple]
+# The target defaults to the Rust host. Needs git, CMake and a C/C++ toolchain.
+set -euo pipefail
+
+WHISPER_VERSION="v1.7.5"
+TARGET="${1:-$(rustc -vV | sed -n 's/^host: //p')}"
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+BINARIES="$ROOT/src-tauri/binaries"
+WORK="${RUNNER_TEMP:-${TMPDIR:-/tmp}}/whisper.cpp-$WHISPER_VERSION"
+
+if [ ! -d "$WORK" ]; then
+  git clone --depth 1 --branch "$WHISPER_VERSION" https://github.com/ggml-org/whisper.cpp "$WORK"
+fi
+
+# Static, portable build: no shared libraries to bundle, no host-only CPU instructions
+CMAKE_ARGS=(
+  -DCMAKE_BUILD_TYPE=Release
+  -DBUILD_SHARED_LIBS=OFF
+  -DGGML_NATIVE=OFF
+  -DWHISPER_BUILD_TESTS=OFF
+  -DWHISPER_BUILD_SERVER=OFF
+)
+# Apple Silicon gets Metal with its shaders built in; Intel Macs run on the CPU
+case "$TARGET" in
+  aarch64-apple-darwin) CMAKE_ARGS+=(-DCMAKE_OSX_ARCHITECTURES=arm64 -DGGML_METAL_EMBED_LIBRARY=ON) ;;
+  x86_64-apple-darwin) CMAKE_ARGS+=(-DCMAKE_OSX_ARCHITECTURES=x86_64 -DGGML_METAL=OFF) ;;
+esac
+
+cmake -S "$WORK" -B "$WORK/build-$TARGET" "${CMAKE_ARGS[@]}"
+cmake --build "$WORK/build-$TARGET" --config Release --target whisper-cli -j 4
+
+mkdir -p "$BINARIES"
+case "$TARGET" in
+  *-windows-*)
+    cp "$WORK/build-$TARGET/bin/Release/whisper-cli.exe" "$BINARIES/whisper-cli-$TARGET.exe"
+    ;;
+  *)
+    cp "$WORK/build-$TARGET/bin/whisper-cli" "$BINARIES/whisper-cli-$TARGET"
+    chmod +x "$BINARIES/whisper-cli-$TARGET"
+    ;;
+esac
+echo "Built $BINARIES/whisper-cli-$TARGET"
@@ -1,3 +1,48 @@
+use std::fs;
+use std::path::Path;
+
+const PLACEHOLDER_MARKER: &str = "whisper-cli was not built";
+
 fn main() {
+    ensure_whisper_sidecar();
     tauri_build::build()
 }
+
+// whisper-cli isn't checked in, and Tauri refuses to build when a bundled binary is
+// missing. Debug builds get a placeholder that explains how to build the real one, so a
+// fresh checkout still builds and tests; release builds, which get bundled, need the real one.
+fn ensure_whisper_sidecar() {
+    let target = std::env::var("TARGET").unwrap();
+    let extension = if target.contains("windows") { ".exe" } else { "" };
+    let sidecar = Path::new("binaries").join(format!("whisper-cli-{}{}", target, extension));
+    println!("cargo:rerun-if-changed={}", sidecar.display());
+
+    let is_placeholder = fs::read(&sidecar)
+        .map(|bytes| String::from_utf8_lossy(&bytes).contains(PLACEHOLDER_MARKER))
+        .unwrap_or(false);
+    if std::env::var("PROFILE").as_deref() == Ok("release") {
+        if !sidecar.exists() || is_placeholder {
+            panic!("whisper-cli is missing; run scripts/build-whisper-cli.sh {} before a release build", target);
+        }
+        return;
+    }
+    if sidecar.exists() {
+        return;
+    }
+
+    println!(
+        "cargo:warning=whisper-cli not found; using a placeholder. Run scripts/build-whisper-cli.sh {} for local transcription.",
+        target
+    );
+    let placeholder = format!(
+        "#!/bin/sh\necho \"{}: run scripts/build-whisper-cli.sh {}\" >&2\nexit 1\n",
+        PLACEHOLDER_MARKER, target
+    );
+    fs::create_dir_all("binaries").unwrap();
+    fs::write(&sidecar, placeholder).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&sidecar, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
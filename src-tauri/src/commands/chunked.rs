@@ -0,0 +1,383 @@
+// src-tauri/src/commands/chunked.rs
+// Transcribing a long recording as separate chunks (see convert.rs) and stitching the
+// results back into one transcript on the original timeline
+
+use futures_util::{stream, StreamExt, TryFutureExt, TryStreamExt};
+use log::info;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::Path;
+use tauri::AppHandle;
+use tokio_util::sync::CancellationToken;
+
+use super::convert::AudioChunk;
+use super::transcribe::{
+    assemblyai_provider, register_wait, submit_boosted, upload_cancellable, wait_tracked, TranscribeError,
+    TranscriptionOptions, DEFAULT_WAIT_TIMEOUT, POLL_RETRY, SUBMIT_RETRY, UPLOAD_RETRY,
+};
+use crate::providers::{
+    Chapter, Entity, IabCategoriesResult, SentimentResult, TranscriptResponse, TranscriptStatus, Utterance, Word,
+};
+
+/// Most chunks uploaded and transcribed at once
+pub const MAX_PARALLEL_CHUNKS: usize = 3;
+
+// Two chunks' copies of the same word in the overlap land within this of each other
+const WORD_MATCH_TOLERANCE_MS: i64 = 500;
+
+/// A finished chunk transcript and where the chunk sits in the recording
+#[derive(Debug)]
+pub struct ChunkTranscript {
+    pub offset_ms: i64,
+    /// Length including the overlap into the next chunk
+    pub duration_ms: i64,
+    pub response: TranscriptResponse,
+}
+
+// AssemblyAI-style labels: A..Z, then AA, AB ...
+fn generic_label(index: usize) -> String {
+    let letter = (b'A' + (index % 26) as u8) as char;
+    if index < 26 {
+        letter.to_string()
+    } else {
+        format!("{}{}", generic_label(index / 26 - 1), letter)
+    }
+}
+
+// Letter labels are per-chunk guesses; names and roles from speaker identification
+// already mean the same person in every chunk
+fn is_generic_label(label: &str) -> bool {
+    !label.is_empty() && label.len() <= 2 && label.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn normalize_word(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Map one chunk's speaker labels onto the labels already used in the merged transcript.
+/// `tail` holds the previous chunk's (already relabelled) words inside the overlap and `head`
+/// this chunk's; speakers who said the same words at the same time are the same person.
+/// Unmatched letter labels get a label not used yet; names and roles are kept as they are.
+pub fn reconcile_speakers(
+    tail: &[(String, Word)],
+    head: &[(String, Word)],
+    labels: &[String],
+    taken: &mut Vec<String>,
+) -> HashMap<String, String> {
+    let mut votes: HashMap<(&str, &str), usize> = HashMap::new();
+    for (head_speaker, head_word) in head {
+        let text = normalize_word(&head_word.text);
+        if text.is_empty() {
+            continue;
+        }
+        let matched = tail.iter().find(|(_, w)| {
+            (w.start - head_word.start).abs() <= WORD_MATCH_TOLERANCE_MS && normalize_word(&w.text) == text
+        });
+        if let Some((tail_speaker, _)) = matched {
+            *votes.entry((head_speaker.as_str(), tail_speaker.as_str())).or_default() += 1;
+        }
+    }
+    
+    // Strongest agreement first, so each earlier speaker is claimed at most once
+    let mut ranked: Vec<((&str, &str), usize)> = votes.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut claimed: HashSet<&str> = HashSet::new();
+    for ((head_speaker, tail_speaker), _) in ranked {
+        if !mapping.contains_key(head_speaker) && claimed.insert(tail_speaker) {
+            mapping.insert(head_speaker.to_string(), tail_speaker.to_string());
+        }
+    }
+    
+    for label in labels {
+        if mapping.contains_key(label) {
+            continue;
+        }
+        let merged = if !is_generic_label(label) || !taken.contains(label) {
+            label.clone()
+        } else {
+            (0..).map(generic_label).find(|l| !taken.contains(l)).unwrap_or_default()
+        };
+        taken.push(merged.clone());
+        mapping.insert(label.clone(), merged);
+    }
+    for merged in mapping.values() {
+        if !taken.contains(merged) {
+            taken.push(merged.clone());
+        }
+    }
+    
+    mapping
+}
+
+fn shift_utterance(utterance: &mut Utterance, offset_ms: i64) {
+    utterance.start += offset_ms;
+    utterance.end += offset_ms;
+    for word in &mut utterance.words {
+        word.start += offset_ms;
+        word.end += offset_ms;
+    }
+}
+
+// Drop what the next chunk transcribes again; None if nothing is left
+fn trim_utterance(mut utterance: Utterance, keep_until: i64) -> Option<Utterance> {
+    if utterance.start >= keep_until {
+        return None;
+    }
+    if utterance.words.iter().all(|w| w.start < keep_until) {
+        return Some(utterance);
+    }
+    
+    utterance.words.retain(|w| w.start < keep_until);
+    let last = utterance.words.last()?;
+    utterance.end = last.end;
+    utterance.text = utterance.words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+    let confidences: Vec<f64> = utterance.words.iter().filter_map(|w| w.confidence).collect();
+    if !confidences.is_empty() {
+        utterance.confidence = Some(confidences.iter().sum::<f64>() / confidences.len() as f64);
+    }
+    Some(utterance)
+}
+
+/// Results other than utterances gathered from several transcripts of one recording,
+/// each already moved onto the recording's timeline
+#[derive(Default)]
+pub(crate) struct MergedResults {
+    sentiments: Vec<SentimentResult>,
+    chapters: Vec<Chapter>,
+    entities: Vec<Entity>,
+    topics: HashMap<String, f64>,
+    summaries: Vec<String>,
+    texts: Vec<String>,
+    language: Option<(Option<String>, Option<f64>)>,
+    transcript_ids: Vec<String>,
+}
+
+impl MergedResults {
+    /// Take one transcript's results; its utterances are merged by the caller
+    pub(crate) fn add(&mut self, response: TranscriptResponse) {
+        self.transcript_ids.push(response.id);
+        self.sentiments.extend(response.sentiment_analysis_results.unwrap_or_default());
+        self.chapters.extend(response.chapters.unwrap_or_default());
+        self.entities.extend(response.entities.unwrap_or_default());
+        // A topic is as relevant as in the transcript that discussed it most
+        for (label, relevance) in response.iab_categories_result.map(|r| r.summary).unwrap_or_default() {
+            let best = self.topics.entry(label).or_insert(relevance);
+            *best = best.max(relevance);
+        }
+        self.summaries.extend(response.summary.filter(|s| !s.trim().is_empty()));
+        self.texts.extend(response.text);
+        if self.language.is_none() && response.language_code.is_some() {
+            self.language = Some((response.language_code, response.language_confidence));
+        }
+    }
+
+    /// Build the merged transcript around `utterances`, listing the transcripts it was
+    /// made from under `ids_key` in `extra`
+    pub(crate) fn finish(mut self, utterances: Vec<Utterance>, ids_key: &str) -> TranscriptResponse {
+        // Stable sorts keep the order added for anything starting at the same moment
+        self.sentiments.sort_by_key(|s| s.start);
+        self.chapters.sort_by_key(|c| c.start);
+        self.entities.sort_by_key(|e| e.start);
+    
+        let text = if utterances.is_empty() {
+            self.texts.join(" ")
+        } else {
+            utterances.iter().map(|u| u.text.as_str()).collect::<Vec<_>>().join(" ")
+        };
+        let (language_code, language_confidence) = self.language.unwrap_or((None, None));
+    
+        let mut extra = HashMap::new();
+        extra.insert(ids_key.to_string(), serde_json::json!(self.transcript_ids));
+    
+        TranscriptResponse {
+            id: self.transcript_ids.first().cloned().unwrap_or_default(),
+            status: TranscriptStatus::Completed,
+            text: Some(text),
+            utterances: Some(utterances),
+            summary: (!self.summaries.is_empty()).then(|| self.summaries.join("\n")),
+            iab_categories_result: (!self.topics.is_empty()).then_some(IabCategoriesResult { summary: self.topics }),
+            sentiment_analysis_results: (!self.sentiments.is_empty()).then_some(self.sentiments),
+            language_code,
+            language_confidence,
+            chapters: (!self.chapters.is_empty()).then_some(self.chapters),
+            entities: (!self.entities.is_empty()).then_some(self.entities),
+            error: None,
+            extra,
+        }
+    }
+}
+
+/// Stitch chunk transcripts into one, with every timestamp on the original recording's
+/// timeline. Each chunk's overlap into the next is only used to match up speakers; the
+/// next chunk's (uncut) copy of that audio is the one kept.
+pub fn merge_chunk_transcripts(mut chunks: Vec<ChunkTranscript>) -> TranscriptResponse {
+    chunks.sort_by_key(|c| c.offset_ms);
+    let offsets: Vec<i64> = chunks.iter().map(|c| c.offset_ms).collect();
+    
+    let mut utterances: Vec<Utterance> = Vec::new();
+    let mut merged = MergedResults::default();
+    
+    let mut taken: Vec<String> = Vec::new();
+    let mut tail: Vec<(String, Word)> = Vec::new();
+    let mut previous_end = i64::MIN;
+    
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        let offset = chunk.offset_ms;
+        let keep_until = offsets.get(index + 1).copied().unwrap_or(i64::MAX);
+        let mut response = chunk.response;
+    
+        let mut chunk_utterances = response.utterances.take().unwrap_or_default();
+        for utterance in &mut chunk_utterances {
+            shift_utterance(utterance, offset);
+        }
+    
+        let mut labels: Vec<String> = Vec::new();
+        for utterance in &chunk_utterances {
+            if !labels.contains(&utterance.speaker) {
+                labels.push(utterance.speaker.clone());
+            }
+        }
+        let head: Vec<(String, Word)> = chunk_utterances
+            .iter()
+            .flat_map(|u| u.words.iter().filter(|w| w.start < previous_end).map(|w| (u.speaker.clone(), w.clone())))
+            .collect();
+        let mapping = reconcile_speakers(&tail, &head, &labels, &mut taken);
+        let relabel = |speaker: &str| mapping.get(speaker).cloned().unwrap_or_else(|| speaker.to_string());
+    
+        for utterance in &mut chunk_utterances {
+            utterance.speaker = relabel(&utterance.speaker);
+            for word in &mut utterance.words {
+                if let Some(speaker) = &word.speaker {
+                    word.speaker = Some(relabel(speaker));
+                }
+            }
+        }
+    
+        tail = chunk_utterances
+            .iter()
+            .flat_map(|u| u.words.iter().filter(|w| w.start >= keep_until).map(|w| (u.speaker.clone(), w.clone())))
+            .collect();
+        previous_end = offset + chunk.duration_ms;
+    
+        utterances.extend(chunk_utterances.into_iter().filter_map(|u| trim_utterance(u, keep_until)));
+    
+        if let Some(sentiments) = &mut response.sentiment_analysis_results {
+            for sentiment in sentiments.iter_mut() {
+                sentiment.start += offset;
+                sentiment.end += offset;
+                sentiment.speaker = sentiment.speaker.as_deref().map(relabel);
+            }
+            sentiments.retain(|s| s.start < keep_until);
+        }
+        if let Some(chapters) = &mut response.chapters {
+            for chapter in chapters.iter_mut() {
+                chapter.start += offset;
+                chapter.end += offset;
+            }
+            chapters.retain(|c| c.start < keep_until);
+        }
+        if let Some(entities) = &mut response.entities {
+            for entity in entities.iter_mut() {
+                entity.start += offset;
+                entity.end += offset;
+            }
+            entities.retain(|e| e.start < keep_until);
+        }
+        merged.add(response);
+    }
+    
+    merged.finish(utterances, "chunk_transcript_ids")
+}
+
+/// Run `task` on every item, at most `limit` at once, returning the results in item order.
+/// The first failure, or `cancel` firing, drops the tasks still running, which stops
+/// their uploads and waits.
+pub async fn run_parallel<T, R, F, Fut>(
+    items: Vec<T>,
+    limit: usize,
+    cancel: &CancellationToken,
+    mut task: F,
+) -> Result<Vec<R>, TranscribeError>
+where
+    F: FnMut(usize, T) -> Fut,
+    Fut: Future<Output = Result<R, TranscribeError>>,
+{
+    let tasks = stream::iter(items.into_iter().enumerate())
+        .map(|(index, item)| Ok(task(index, item).map_ok(move |result| (index, result))))
+        .try_buffer_unordered(limit)
+        .try_collect::<Vec<(usize, R)>>();
+    
+    let mut results = tokio::select! {
+        _ = cancel.cancelled() => return Err(TranscribeError::Cancelled),
+        results = tasks => results?,
+    };
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// Upload, submit and wait for each file in parallel (at most `MAX_PARALLEL_CHUNKS` at
+/// once), returning the transcripts in the order of `paths`. The whole job is registered
+/// as a wait under `job_id`, so `cancel_transcription_wait(job_id)` stops every file
+/// wherever it has got to; each file reports upload progress as `<job_id>#<n>`.
+pub(crate) async fn transcribe_in_parallel(
+    app: &AppHandle,
+    paths: &[String],
+    api_key: String,
+    options: &TranscriptionOptions,
+    job_id: &str,
+) -> Result<Vec<TranscriptResponse>, TranscribeError> {
+    let uploader = assemblyai_provider(app, api_key.clone(), UPLOAD_RETRY);
+    let submitter = assemblyai_provider(app, api_key.clone(), SUBMIT_RETRY);
+    let poller = assemblyai_provider(app, api_key, POLL_RETRY);
+    let cancel_token = CancellationToken::new();
+    let _registration = register_wait(app, job_id, &cancel_token);
+    
+    let (uploader, submitter, poller) = (&uploader, &submitter, &poller);
+    run_parallel(paths.iter().collect(), MAX_PARALLEL_CHUNKS, &cancel_token, |index, path| {
+        let key = format!("{}#{}", job_id, index);
+        async move {
+            let upload_url = upload_cancellable(app, uploader, Path::new(path), &key).await?;
+            let transcript_id = submit_boosted(app, submitter, &upload_url, options).await?;
+            info!("{} submitted as {}", key, transcript_id);
+            wait_tracked(app, poller, &key, &transcript_id, DEFAULT_WAIT_TIMEOUT, |_| {}).await
+        }
+    })
+    .await
+}
+
+/// Transcribe the chunks of a split recording in parallel and merge them into one transcript.
+/// `cancel_transcription_wait(job_id)` stops every chunk, and the first chunk to fail
+/// stops the rest. The merged transcript lists the AssemblyAI transcript of every chunk
+/// in `chunk_transcript_ids`.
+#[tauri::command]
+pub async fn transcribe_chunks(
+    app: AppHandle,
+    chunks: Vec<AudioChunk>,
+    api_key: String,
+    options: TranscriptionOptions,
+    job_id: String,
+) -> Result<TranscriptResponse, TranscribeError> {
+    info!("Transcribing {} chunks for job {}", chunks.len(), job_id);
+    
+    let paths: Vec<String> = chunks.iter().map(|c| c.path.clone()).collect();
+    let responses = transcribe_in_parallel(&app, &paths, api_key, &options, &job_id).await?;
+    let chunk_transcripts = chunks
+        .into_iter()
+        .zip(responses)
+        .map(|(chunk, response)| ChunkTranscript {
+            offset_ms: chunk.offset_ms,
+            duration_ms: chunk.duration_ms,
+            response,
+        })
+        .collect();
+    let merged = merge_chunk_transcripts(chunk_transcripts);
+    info!(
+        "Merged {} chunk transcripts for job {} ({} utterances)",
+        merged.extra.get("chunk_transcript_ids").and_then(|ids| ids.as_array()).map_or(0, |ids| ids.len()),
+        job_id,
+        merged.utterances.as_ref().map_or(0, |u| u.len())
+    );
+    Ok(merged)
+}
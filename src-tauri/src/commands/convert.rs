@@ -1,10 +1,16 @@
-use log::{info, error};
-use std::path::PathBuf;
-use tauri::AppHandle;
+use log::{info, warn, error};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::CommandEvent;
 use tauri_plugin_shell::ShellExt;
-use tempfile::TempDir;
 use thiserror::Error;
 
+use super::media_info::{inspect_media, MediaInfo};
+use super::timeline::{normalize_ranges, session_files, SessionFile, TimeRange, TimelineMap};
+use crate::temp_workspace::{TempWorkspaceError, TempWorkspaces};
+
 #[derive(Error, Debug)]
 pub enum ConvertError {
     #[error("FFmpeg execution failed: {0}")]
@@ -13,6 +19,10 @@ pub enum ConvertError {
     TempDirError(#[from] std::io::Error),
     #[error("Invalid file path: {0}")]
     InvalidPath(String),
+    #[error("Invalid audio selection: {0}")]
+    InvalidSelection(String),
+    #[error("{0}")]
+    InvalidRange(#[from] super::timeline::TimelineError),
 }
 
 impl serde::Serialize for ConvertError {
@@ -29,17 +39,710 @@ impl serde::Serialize for ConvertError {
 pub struct ConversionResult {
     pub output_path: String,
     pub temp_dir: String,
+    /// Pieces of `output_path` to transcribe separately; empty unless splitting was
+    /// requested and the recording is longer than `max_chunk_secs`
+    pub chunks: Vec<AudioChunk>,
+    /// Where the output's audio sits in the source; `None` when it is the whole source
+    pub timeline: Option<TimelineMap>,
+    /// For a session joined from several files, where each starts; empty otherwise
+    pub files: Vec<SessionFile>,
+}
+
+/// Which audio to transcribe from a multi-track or multi-channel recording.
+/// The default takes the stream FFmpeg picks and downmixes all its channels.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSelection {
+    /// Audio streams to mix, by file index as reported by `inspect_media`
+    pub streams: Vec<u32>,
+    /// Keep only this channel (0-based) of the selected stream, e.g. one participant
+    /// recorded on the left channel
+    pub channel: Option<u32>,
+}
+
+/// Named FFmpeg filter chains that clean up speech before it is transcribed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CleanupProfile {
+    /// Convert the audio as it is
+    #[default]
+    None,
+    /// Bring the recording to a steady speech loudness
+    Normalize,
+    /// Cut low rumble and steady background noise (fans, air conditioning)
+    Denoise,
+    /// Denoise, then lift quiet voices such as a far-end speaker on a room microphone
+    ConferenceRoom,
+}
+
+impl CleanupProfile {
+    pub const ALL: [Self; 4] = [Self::None, Self::Normalize, Self::Denoise, Self::ConferenceRoom];
+    
+    /// FFmpeg audio filters to run, in order
+    pub fn filters(self) -> &'static [&'static str] {
+        match self {
+            Self::None => &[],
+            Self::Normalize => &["loudnorm=I=-16:TP=-1.5:LRA=11"],
+            Self::Denoise => &["highpass=f=80", "afftdn=nf=-25"],
+            // dynaudnorm evens out speakers over a few seconds; loudnorm then sets the overall level
+            Self::ConferenceRoom => &[
+                "highpass=f=100",
+                "afftdn=nf=-25",
+                "dynaudnorm=f=150:g=15:p=0.9",
+                "loudnorm=I=-16:TP=-1.5:LRA=11",
+            ],
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::None => "No cleanup",
+            Self::Normalize => "Normalize loudness",
+            Self::Denoise => "Reduce noise",
+            Self::ConferenceRoom => "Conference room",
+        }
+    }
+}
+
+/// A cleanup profile as shown in the UI
+#[derive(Debug, Serialize)]
+pub struct CleanupProfileInfo {
+    pub id: CleanupProfile,
+    pub label: &'static str,
+    pub filters: Vec<&'static str>,
+}
+
+/// FFmpeg arguments that route the selected audio to the output; empty for the default
+pub fn audio_selection_args(selection: &AudioSelection) -> Result<Vec<String>, ConvertError> {
+    audio_filter_args(selection, &[])
+}
+
+/// Like `audio_selection_args`, also running the selected audio through `filters` in order
+pub fn audio_filter_args(selection: &AudioSelection, filters: &[String]) -> Result<Vec<String>, ConvertError> {
+    let mut streams = selection.streams.clone();
+    streams.sort_unstable();
+    streams.dedup();
+    
+    let chain = filters.join(",");
+    // A stream picked with -map can take a simple -af chain
+    let simple = |mut args: Vec<String>| {
+        if !chain.is_empty() {
+            args.extend(["-af".to_string(), chain.clone()]);
+        }
+        args
+    };
+    // Audio built in a filter graph has to be filtered inside it
+    let graph = |head: String, label: &str| {
+        let body = if chain.is_empty() { head } else { format!("{},{}", head, chain) };
+        vec![
+            "-filter_complex".to_string(),
+            format!("{}[{}]", body, label),
+            "-map".to_string(),
+            format!("[{}]", label),
+        ]
+    };
+    
+    let args = match (streams.as_slice(), selection.channel) {
+        ([], None) => simple(Vec::new()),
+        ([index], None) => simple(vec!["-map".to_string(), format!("0:{}", index)]),
+        (_, Some(channel)) if streams.len() <= 1 => {
+            // With no stream given, take the channel from the first audio stream
+            let input = streams.first().map_or("0:a:0".to_string(), |i| format!("0:{}", i));
+            graph(format!("[{}]pan=mono|c0=c{}", input, channel), "picked")
+        }
+        (_, Some(_)) => {
+            return Err(ConvertError::InvalidSelection(
+                "A channel can only be picked from a single stream".to_string(),
+            ));
+        }
+        (indices, None) => {
+            // normalize=0 keeps each track at its own level instead of dividing by the count
+            let inputs: String = indices.iter().map(|i| format!("[0:{}]", i)).collect();
+            graph(format!("{}amix=inputs={}:normalize=0", inputs, indices.len()), "mixed")
+        }
+    };
+    Ok(args)
+}
+
+// Milliseconds as FFmpeg seconds
+fn ffmpeg_secs(ms: i64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+/// FFmpeg arguments that keep only `ranges` (normalized) of the input: input options that
+/// seek to the first range and stop after the last, plus a filter that drops the gaps
+/// between them when there is more than one
+pub fn trim_args(ranges: &[TimeRange]) -> (Vec<String>, Option<String>) {
+    let (Some(first), Some(last)) = (ranges.first(), ranges.last()) else {
+        return (Vec::new(), None);
+    };
+    let offset = first.start_ms;
+    let input_args = vec![
+        "-ss".to_string(),
+        ffmpeg_secs(offset),
+        "-t".to_string(),
+        ffmpeg_secs(last.end_ms - offset),
+    ];
+    if ranges.len() == 1 {
+        return (input_args, None);
+    }
+    // Times restart at 0 after seeking
+    (input_args, select_filter(ranges, offset))
+}
+
+// aselect filter keeping `ranges` of audio whose time 0 is `offset_ms` in range time;
+// asetpts closes the gaps aselect leaves
+fn select_filter(ranges: &[TimeRange], offset_ms: i64) -> Option<String> {
+    if ranges.is_empty() {
+        return None;
+    }
+    let keep = ranges
+        .iter()
+        .map(|r| format!("between(t,{},{})", ffmpeg_secs(r.start_ms - offset_ms), ffmpeg_secs(r.end_ms - offset_ms)))
+        .collect::<Vec<_>>()
+        .join("+");
+    Some(format!("aselect='{}',asetpts=N/SR/TB", keep))
+}
+
+/// FFmpeg arguments that join the main audio of `count` inputs end to end and run the
+/// result through `filters` in order. Each input is brought to the output format first,
+/// since the concat filter needs them all to match.
+pub fn concat_filter_args(count: usize, filters: &[String]) -> Vec<String> {
+    let mut graph: String = (0..count)
+        .map(|i| format!("[{}:a:0]aformat=sample_fmts=fltp:sample_rates=16000:channel_layouts=mono[p{}];", i, i))
+        .collect();
+    graph.extend((0..count).map(|i| format!("[p{}]", i)));
+    graph.push_str(&format!("concat=n={}:v=0:a=1", count));
+    for filter in filters {
+        graph.push(',');
+        graph.push_str(filter);
+    }
+    graph.push_str("[session]");
+    vec![
+        "-filter_complex".to_string(),
+        graph,
+        "-map".to_string(),
+        "[session]".to_string(),
+    ]
+}
+
+/// Filter graph mixing the main audio of `count` inputs that were recorded side by side
+/// (one track per participant) into one, without lowering any of them.
+pub fn mix_filter_args(count: usize) -> Vec<String> {
+    let mut graph: String = (0..count).map(|i| format!("[{}:a:0]", i)).collect();
+    graph.push_str(&format!("amix=inputs={}:duration=longest:normalize=0[mixed]", count));
+    vec![
+        "-filter_complex".to_string(),
+        graph,
+        "-map".to_string(),
+        "[mixed]".to_string(),
+    ]
+}
+
+/// What to take from the input and how to prepare it. The defaults convert the main
+/// audio of the whole recording as it is.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConvertOptions {
+    /// Streams or channel to keep; by default the main stream is downmixed
+    pub audio: Option<AudioSelection>,
+    /// Keep only these parts of the source, joined end to end
+    pub ranges: Vec<TimeRange>,
+    /// Filter profile to run the audio through, e.g. to lift a quiet speaker
+    pub cleanup: CleanupProfile,
+    /// Cut long silences out
+    pub strip_silence: Option<SilenceOptions>,
+}
+
+/// How to split long recordings into chunks at silence points
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SplitOptions {
+    /// Preferred chunk length; the cut goes at the silence nearest to it
+    pub target_chunk_secs: u64,
+    /// Hard limit; with no silence before it the audio is cut here
+    pub max_chunk_secs: u64,
+    /// Audio repeated at the start of the next chunk, used to match speakers across chunks
+    pub overlap_secs: u64,
+    /// Quieter than this counts as silence
+    pub silence_threshold_db: f64,
+    /// Shortest pause that counts as a silence point
+    pub min_silence_secs: f64,
+}
+
+impl Default for SplitOptions {
+    fn default() -> Self {
+        Self {
+            target_chunk_secs: 30 * 60,
+            max_chunk_secs: 40 * 60,
+            overlap_secs: 20,
+            silence_threshold_db: -35.0,
+            min_silence_secs: 0.6,
+        }
+    }
+}
+
+/// A pause found by FFmpeg's silencedetect filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Silence {
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+/// How to cut long silences (breaks, waiting for people to join) out of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SilenceOptions {
+    /// Quieter than this counts as silence
+    pub threshold_db: f64,
+    /// Only silences at least this long are cut
+    pub min_silence_secs: f64,
+    /// Silence left on each side of a cut so the words around it aren't clipped
+    pub padding_secs: f64,
+}
+
+impl Default for SilenceOptions {
+    fn default() -> Self {
+        Self {
+            threshold_db: -40.0,
+            min_silence_secs: 10.0,
+            padding_secs: 1.0,
+        }
+    }
+}
+
+/// The parts of `ranges` (normalized; the whole `duration_ms` when empty) left after
+/// cutting out `silences`, less `padding_secs` at each end. Returns `ranges` unchanged
+/// when nothing is cut.
+pub fn strip_silences(
+    ranges: &[TimeRange],
+    duration_ms: i64,
+    silences: &[Silence],
+    options: &SilenceOptions,
+) -> Vec<TimeRange> {
+    let min_ms = (options.min_silence_secs * 1000.0).round() as i64;
+    let padding_ms = (options.padding_secs.max(0.0) * 1000.0).round() as i64;
+    let cuts: Vec<(i64, i64)> = silences
+        .iter()
+        .filter(|s| s.end_ms - s.start_ms >= min_ms)
+        .map(|s| (s.start_ms + padding_ms, s.end_ms - padding_ms))
+        .filter(|(start, end)| end > start)
+        .collect();
+    
+    let whole = [TimeRange { start_ms: 0, end_ms: duration_ms }];
+    let base = if ranges.is_empty() { &whole[..] } else { ranges };
+    let mut kept = Vec::new();
+    for range in base {
+        let mut start = range.start_ms;
+        for &(cut_start, cut_end) in &cuts {
+            if cut_end <= start || cut_start >= range.end_ms {
+                continue;
+            }
+            if cut_start > start {
+                kept.push(TimeRange { start_ms: start, end_ms: cut_start });
+            }
+            start = start.max(cut_end);
+        }
+        if range.end_ms > start {
+            kept.push(TimeRange { start_ms: start, end_ms: range.end_ms });
+        }
+    }
+    
+    if kept.as_slice() == base {
+        ranges.to_vec()
+    } else {
+        kept
+    }
+}
+
+/// Where a chunk sits in the original recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkSpan {
+    pub offset_ms: i64,
+    pub duration_ms: i64,
+}
+
+/// One piece of a split recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioChunk {
+    pub path: String,
+    /// Start of the chunk in the original recording
+    pub offset_ms: i64,
+    /// Length including the overlap into the next chunk
+    pub duration_ms: i64,
+}
+
+// "12.345" seconds -> milliseconds
+fn parse_secs(value: &str) -> Option<i64> {
+    value.trim().parse::<f64>().ok().map(|secs| (secs.max(0.0) * 1000.0).round() as i64)
+}
+
+// "  Duration: 01:02:03.45, start: 0.000000, bitrate: 32 kb/s" -> milliseconds
+pub(crate) fn parse_duration_line(line: &str) -> Option<i64> {
+    let rest = line.trim_start().strip_prefix("Duration: ")?;
+    let mut parts = rest.split(',').next().unwrap_or("").split(':');
+    let (h, m, s) = (parts.next()?, parts.next()?, parts.next()?);
+    let (h, m, s) = (h.parse::<i64>().ok()?, m.parse::<i64>().ok()?, parse_secs(s)?);
+    Some((h * 3600 + m * 60) * 1000 + s)
+}
+
+/// Pull the total duration and the detected silences out of FFmpeg's stderr
+/// from a `-af silencedetect` run
+pub fn parse_silence_log(stderr: &str) -> (Option<i64>, Vec<Silence>) {
+    let mut duration_ms = None;
+    let mut silences = Vec::new();
+    let mut silence_start = None;
+    
+    for line in stderr.lines() {
+        if let Some(ms) = parse_duration_line(line) {
+            duration_ms = Some(ms);
+        } else if let Some((_, value)) = line.split_once("silence_start: ") {
+            silence_start = parse_secs(value);
+        } else if let Some((_, value)) = line.split_once("silence_end: ") {
+            let end = value.split('|').next().and_then(parse_secs);
+            if let (Some(start_ms), Some(end_ms)) = (silence_start.take(), end) {
+                silences.push(Silence { start_ms, end_ms });
+            }
+        }
+    }
+    
+    (duration_ms, silences)
+}
+
+/// Decide where to cut a recording of `duration_ms`.
+/// Each cut goes at the middle of the silence closest to `target_chunk_secs`, looking no
+/// earlier than half the target and no later than `max_chunk_secs`; with no silence in
+/// that window the cut is made at the maximum. Every chunk but the last runs on by
+/// `overlap_secs` into the next.
+pub fn plan_chunks(duration_ms: i64, silences: &[Silence], options: &SplitOptions) -> Vec<ChunkSpan> {
+    let target_ms = options.target_chunk_secs.max(1) as i64 * 1000;
+    let max_ms = (options.max_chunk_secs as i64 * 1000).max(target_ms);
+    let overlap_ms = options.overlap_secs as i64 * 1000;
+    
+    let mut boundaries = vec![0];
+    let mut start = 0;
+    while duration_ms - start > max_ms {
+        let earliest = start + target_ms / 2;
+        let latest = start + max_ms;
+        let cut = silences
+            .iter()
+            .map(|s| (s.start_ms + s.end_ms) / 2)
+            .filter(|mid| *mid > earliest && *mid <= latest)
+            .min_by_key(|mid| (mid - (start + target_ms)).abs())
+            .unwrap_or(latest);
+        boundaries.push(cut);
+        start = cut;
+    }
+    boundaries.push(duration_ms);
+    
+    boundaries
+        .windows(2)
+        .map(|pair| {
+            let end = if pair[1] == duration_ms { duration_ms } else { (pair[1] + overlap_ms).min(duration_ms) };
+            ChunkSpan {
+                offset_ms: pair[0],
+                duration_ms: end - pair[0],
+            }
+        })
+        .collect()
+}
+
+/// Event name used for FFmpeg conversion progress
+pub const CONVERSION_PROGRESS_EVENT: &str = "conversion-progress";
+
+/// Conversion progress, emitted as `conversion-progress`
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ConversionProgress {
+    pub job_id: String,
+    /// 0-100; absent until FFmpeg has logged the input duration
+    pub percent: Option<f64>,
+    pub processed_ms: i64,
+    pub duration_ms: Option<i64>,
+    /// Seconds left at the average rate so far
+    pub eta_secs: Option<u64>,
+}
+
+/// Follows an FFmpeg run from its `-progress` output and the input duration in its log
+#[derive(Debug, Default)]
+pub struct ProgressTracker {
+    duration_ms: Option<i64>,
+    processed_ms: i64,
+    finished: bool,
+}
+
+impl ProgressTracker {
+    /// Read a line of FFmpeg's log (stderr); the first `Duration:` is the input's
+    pub fn read_log_line(&mut self, line: &str) {
+        if self.duration_ms.is_none() {
+            self.duration_ms = parse_duration_line(line);
+        }
+    }
+    
+    /// Read a line of `-progress` output. Returns true at the end of each progress block.
+    pub fn read_progress_line(&mut self, line: &str) -> bool {
+        match line.trim().split_once('=') {
+            // Reported as "N/A" until the first frame is written
+            Some(("out_time_us", value)) => {
+                if let Ok(us) = value.parse::<i64>() {
+                    self.processed_ms = us.max(0) / 1000;
+                }
+                false
+            }
+            Some(("progress", value)) => {
+                self.finished = value == "end";
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    pub fn percent(&self) -> Option<f64> {
+        if self.finished {
+            return Some(100.0);
+        }
+        let duration_ms = self.duration_ms.filter(|d| *d > 0)?;
+        Some((self.processed_ms as f64 / duration_ms as f64 * 100.0).clamp(0.0, 100.0))
+    }
+    
+    /// Time left if the rest converts at the same rate as the part done in `elapsed`
+    pub fn eta(&self, elapsed: Duration) -> Option<Duration> {
+        let percent = self.percent().filter(|p| *p > 0.0)?;
+        Some(elapsed.mul_f64((100.0 - percent) / percent))
+    }
+    
+    pub fn snapshot(&self, job_id: &str, elapsed: Duration) -> ConversionProgress {
+        ConversionProgress {
+            job_id: job_id.to_string(),
+            percent: self.percent(),
+            processed_ms: self.processed_ms,
+            duration_ms: self.duration_ms,
+            eta_secs: self.eta(elapsed).map(|d| d.as_secs_f64().round() as u64),
+        }
+    }
+}
+
+// Run FFmpeg with `-progress pipe:1`, streaming its output and emitting
+// `conversion-progress` events for `job_id`. Returns FFmpeg's log (stderr) on success.
+async fn run_ffmpeg_with_progress(
+    app: &AppHandle,
+    args: &[&str],
+    job_id: Option<&str>,
+    duration_ms: Option<i64>,
+) -> Result<String, ConvertError> {
+    let (mut events, _child) = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| {
+            error!("Failed to create FFmpeg sidecar: {}", e);
+            ConvertError::FfmpegFailed(format!("Failed to start FFmpeg: {}", e))
+        })?
+        .args(["-progress", "pipe:1", "-nostats"])
+        .args(args)
+        .spawn()
+        .map_err(|e| {
+            error!("FFmpeg execution failed: {}", e);
+            ConvertError::FfmpegFailed(format!("FFmpeg failed to execute: {}", e))
+        })?;
+    
+    let started = Instant::now();
+    let mut tracker = ProgressTracker {
+        duration_ms,
+        ..ProgressTracker::default()
+    };
+    let mut stderr = String::new();
+    let mut exit_code = None;
+    
+    while let Some(event) = events.recv().await {
+        match event {
+            CommandEvent::Stdout(bytes) => {
+                for line in String::from_utf8_lossy(&bytes).lines() {
+                    if !tracker.read_progress_line(line) {
+                        continue;
+                    }
+                    if let Some(job_id) = job_id {
+                        let progress = tracker.snapshot(job_id, started.elapsed());
+                        if let Err(e) = app.emit(CONVERSION_PROGRESS_EVENT, progress) {
+                            warn!("Failed to emit conversion progress: {}", e);
+                        }
+                    }
+                }
+            }
+            CommandEvent::Stderr(bytes) => {
+                for line in String::from_utf8_lossy(&bytes).lines() {
+                    tracker.read_log_line(line);
+                    stderr.push_str(line);
+                    stderr.push('\n');
+                }
+            }
+            CommandEvent::Error(e) => {
+                return Err(ConvertError::FfmpegFailed(format!("FFmpeg failed to execute: {}", e)));
+            }
+            CommandEvent::Terminated(payload) => exit_code = payload.code,
+            _ => {}
+        }
+    }
+    
+    if exit_code != Some(0) {
+        error!("FFmpeg exited with code {:?}", exit_code);
+        error!("FFmpeg stderr: {}", stderr);
+        return Err(ConvertError::FfmpegFailed(format!(
+            "FFmpeg exited with code {:?}: {}",
+            exit_code, stderr
+        )));
+    }
+    Ok(stderr)
+}
+
+// Run FFmpeg to completion, returning its stderr (where it logs) on success
+async fn run_ffmpeg(app: &AppHandle, args: &[&str]) -> Result<String, ConvertError> {
+    let output = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| ConvertError::FfmpegFailed(format!("Failed to start FFmpeg: {}", e)))?
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| ConvertError::FfmpegFailed(format!("FFmpeg failed to execute: {}", e)))?;
+    
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if !output.status.success() {
+        error!("FFmpeg stderr: {}", stderr);
+        return Err(ConvertError::FfmpegFailed(format!(
+            "FFmpeg exited with code {:?}: {}",
+            output.status.code(),
+            stderr
+        )));
+    }
+    Ok(stderr)
+}
+
+// Find silences in the converted audio and cut it into chunk files next to it
+async fn split_audio(
+    app: &AppHandle,
+    audio_path: &Path,
+    options: &SplitOptions,
+) -> Result<Vec<AudioChunk>, ConvertError> {
+    let audio_str = audio_path.to_string_lossy().to_string();
+    let filter = format!(
+        "silencedetect=noise={}dB:d={}",
+        options.silence_threshold_db, options.min_silence_secs
+    );
+    let log = run_ffmpeg(app, &["-i", &audio_str, "-af", &filter, "-f", "null", "-"]).await?;
+    
+    let (duration_ms, silences) = parse_silence_log(&log);
+    let duration_ms = duration_ms
+        .ok_or_else(|| ConvertError::FfmpegFailed("Could not read the audio duration".to_string()))?;
+    
+    let spans = plan_chunks(duration_ms, &silences, options);
+    if spans.len() < 2 {
+        return Ok(Vec::new());
+    }
+    info!("Splitting {} ms of audio into {} chunks ({} silences found)", duration_ms, spans.len(), silences.len());
+    
+    let dir = audio_path.parent().unwrap_or(Path::new("."));
+    let mut chunks = Vec::new();
+    for (index, span) in spans.iter().enumerate() {
+        let chunk_path = dir.join(format!("chunk_{:03}.m4a", index));
+        let chunk_str = chunk_path.to_string_lossy().to_string();
+        let offset = format!("{:.3}", span.offset_ms as f64 / 1000.0);
+        let duration = format!("{:.3}", span.duration_ms as f64 / 1000.0);
+    
+        // -ss before -i seeks the input; -c copy avoids re-encoding
+        run_ffmpeg(app, &["-ss", &offset, "-i", &audio_str, "-t", &duration, "-c", "copy", "-y", &chunk_str]).await?;
+        chunks.push(AudioChunk {
+            path: chunk_str,
+            offset_ms: span.offset_ms,
+            duration_ms: span.duration_ms,
+        });
+    }
+    
+    Ok(chunks)
+}
+
+// silencedetect filter for the silences `options` would cut
+fn silence_filter(options: &SilenceOptions) -> String {
+    format!("silencedetect=noise={}dB:d={}", options.threshold_db, options.min_silence_secs)
+}
+
+// Run FFmpeg over `input_args` with `route_args` (which must include silence_filter)
+// and read the durations and silences it reports
+async fn detect_silences(
+    app: &AppHandle,
+    input_args: &[String],
+    route_args: &[String],
+) -> Result<(Option<i64>, Vec<Silence>), ConvertError> {
+    let mut args: Vec<&str> = input_args.iter().map(String::as_str).collect();
+    args.extend(route_args.iter().map(String::as_str));
+    args.extend(["-vn", "-f", "null", "-"]);
+    let log = run_ffmpeg(app, &args).await?;
+    Ok(parse_silence_log(&log))
+}
+
+// Find long silences in the selected audio of the input and narrow `ranges` to the rest
+async fn find_speech(
+    app: &AppHandle,
+    input_path: &str,
+    selection: &AudioSelection,
+    ranges: &[TimeRange],
+    options: &SilenceOptions,
+) -> Result<Vec<TimeRange>, ConvertError> {
+    // Read from the start of the first range to the end of the last; silences
+    // in the gaps between ranges don't matter
+    let (mut input_args, _) = trim_args(ranges);
+    input_args.extend(["-i".to_string(), input_path.to_string()]);
+    let route_args = audio_filter_args(selection, &[silence_filter(options)])?;
+    let (duration_ms, mut silences) = detect_silences(app, &input_args, &route_args).await?;
+    let duration_ms = duration_ms
+        .ok_or_else(|| ConvertError::FfmpegFailed("Could not read the input duration".to_string()))?;
+    // After seeking, FFmpeg's times start from the seek point
+    let offset = ranges.first().map_or(0, |r| r.start_ms);
+    for silence in &mut silences {
+        silence.start_ms += offset;
+        silence.end_ms += offset;
+    }
+    
+    let kept = strip_silences(ranges, duration_ms, &silences, options);
+    info!("Found {} silences; keeping {} part(s) of the input", silences.len(), kept.len());
+    Ok(kept)
+}
+
+// Inspect an input, refusing it if there's no audio to convert (e.g. a screen recording
+// without sound), which FFmpeg would otherwise fail on with a cryptic error
+async fn inspect_input(app: &AppHandle, path: &str) -> Result<MediaInfo, ConvertError> {
+    let media = inspect_media(app.clone(), path.to_string())
+        .await
+        .map_err(|e| ConvertError::InvalidPath(format!("{}: {}", path, e)))?;
+    if !media.has_audio() {
+        return Err(ConvertError::InvalidPath(format!("No audio track found in {}", path)));
+    }
+    Ok(media)
+}
+
+// Total length of `ranges`
+fn ranges_length(ranges: &[TimeRange]) -> i64 {
+    ranges.iter().map(|r| r.end_ms - r.start_ms).sum()
 }
 
 /// Convert a video or audio file to a compressed MP3 suitable for transcription.
 /// Settings: mono, 16kHz, 32kbps - optimized for small file size while maintaining transcription accuracy.
+/// With `split`, long recordings are also cut into chunks at silence points.
+/// With a `job_id`, progress is emitted as `conversion-progress` events tagged with it.
+/// `options` pick the audio, ranges and cleanup. When only parts of the source are kept
+/// (`ranges`, or cutting silences) the result's `timeline` maps the output back to source times.
 #[tauri::command]
 pub async fn convert_to_audio(
     app: AppHandle,
     input_path: String,
+    split: Option<SplitOptions>,
+    job_id: Option<String>,
+    options: Option<ConvertOptions>,
 ) -> Result<ConversionResult, ConvertError> {
     info!("Starting conversion for: {}", input_path);
     
+    let options = options.unwrap_or_default();
+    let mut ranges = normalize_ranges(&options.ranges)?;
+    let audio = options.audio.unwrap_or_default();
+    
     let input = PathBuf::from(&input_path);
     
     // Validate input file exists
@@ -49,6 +752,7 @@ pub async fn convert_to_audio(
     }
     
     info!("Input file exists: {}", input_path);
+    inspect_input(&app, &input_path).await?;
     
     // Get the filename without extension
     let filename = input
@@ -61,68 +765,219 @@ pub async fn convert_to_audio(
     
     info!("Filename: {}", filename);
     
-    // Create a temp directory for the output
-    let temp_dir = TempDir::new()?;
-    let temp_dir_path = temp_dir.path().to_string_lossy().to_string();
+    if let Some(silence) = &options.strip_silence {
+        ranges = find_speech(&app, &input_path, &audio, &ranges, silence).await?;
+    }
+    let (mut input_args, trim_filter) = trim_args(&ranges);
+    input_args.extend(["-i".to_string(), input_path.clone()]);
+    // Trim first so the cleanup filters only measure the audio that is kept
+    let mut filters: Vec<String> = trim_filter.into_iter().collect();
+    filters.extend(options.cleanup.filters().iter().map(|f| f.to_string()));
+    let routing = AudioRouting {
+        input_args,
+        map_args: audio_filter_args(&audio, &filters)?,
+        duration_ms: (!ranges.is_empty()).then(|| ranges_length(&ranges)),
+    };
     
-    info!("Created temp directory: {}", temp_dir_path);
+    let (temp_dir, output_path, chunks) = convert_in_workspace(&app, filename, &routing, split.as_ref(), job_id.as_deref()).await?;
+    Ok(ConversionResult {
+        output_path,
+        temp_dir,
+        chunks,
+        timeline: (!ranges.is_empty()).then(|| TimelineMap::from_ranges(&ranges)),
+        files: Vec::new(),
+    })
+}
+
+/// Join several recordings of one meeting (e.g. a Zoom or Teams call saved in parts) into
+/// one converted file, in the order given. Each file's main audio is used. `ranges` and
+/// cutting silences apply to the joined session's time, and the result's `files` say where
+/// each recording starts in it.
+#[tauri::command]
+pub async fn convert_session(
+    app: AppHandle,
+    input_paths: Vec<String>,
+    split: Option<SplitOptions>,
+    job_id: Option<String>,
+    options: Option<ConvertOptions>,
+) -> Result<ConversionResult, ConvertError> {
+    info!("Starting session conversion for {} files", input_paths.len());
+    
+    let options = options.unwrap_or_default();
+    let mut ranges = normalize_ranges(&options.ranges)?;
+    if options.audio.as_ref().is_some_and(|a| *a != AudioSelection::default()) {
+        return Err(ConvertError::InvalidSelection(
+            "Tracks and channels can't be picked when joining files".to_string(),
+        ));
+    }
+    let first = input_paths
+        .first()
+        .ok_or_else(|| ConvertError::InvalidPath("No files to join".to_string()))?;
+    let filename = PathBuf::from(first)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|stem| format!("{}-session", stem))
+        .ok_or_else(|| ConvertError::InvalidPath(format!("Invalid filename: {}", first)))?;
+    
+    // The durations set the file boundaries, so every file has to be readable up front
+    let mut durations_ms = Vec::new();
+    for path in &input_paths {
+        let media = inspect_input(&app, path).await?;
+        let duration_ms = media
+            .duration_ms
+            .ok_or_else(|| ConvertError::InvalidPath(format!("Could not read the duration of {}", path)))?;
+        durations_ms.push(duration_ms);
+    }
+    let files = session_files(&input_paths, &durations_ms);
+    let total_ms: i64 = durations_ms.iter().sum();
+    
+    let input_args: Vec<String> = input_paths
+        .iter()
+        .flat_map(|path| ["-i".to_string(), path.clone()])
+        .collect();
+    if let Some(silence) = &options.strip_silence {
+        let route_args = concat_filter_args(input_paths.len(), &[silence_filter(silence)]);
+        let (_, silences) = detect_silences(&app, &input_args, &route_args).await?;
+        ranges = strip_silences(&ranges, total_ms, &silences, silence);
+        info!("Found {} silences; keeping {} part(s) of the session", silences.len(), ranges.len());
+    }
+    // Inputs can't be seeked as one, so ranges are always selected by filter
+    let mut filters: Vec<String> = select_filter(&ranges, 0).into_iter().collect();
+    filters.extend(options.cleanup.filters().iter().map(|f| f.to_string()));
+    let routing = AudioRouting {
+        input_args,
+        map_args: concat_filter_args(input_paths.len(), &filters),
+        duration_ms: Some(if ranges.is_empty() { total_ms } else { ranges_length(&ranges) }),
+    };
+    
+    let (temp_dir, output_path, chunks) = convert_in_workspace(&app, &filename, &routing, split.as_ref(), job_id.as_deref()).await?;
+    Ok(ConversionResult {
+        output_path,
+        temp_dir,
+        chunks,
+        timeline: (!ranges.is_empty()).then(|| TimelineMap::from_ranges(&ranges)),
+        files,
+    })
+}
+
+/// Mix per-participant tracks into one file for playback alongside a multitrack
+/// transcript. The tracks are expected to start together, as Zoom and podcast
+/// recorders save them.
+#[tauri::command]
+pub async fn convert_mixdown(
+    app: AppHandle,
+    input_paths: Vec<String>,
+    job_id: Option<String>,
+) -> Result<ConversionResult, ConvertError> {
+    info!("Mixing {} tracks into one", input_paths.len());
     
-    // Keep the temp dir alive by leaking it (we'll clean up later via the frontend)
-    let temp_dir = Box::leak(Box::new(temp_dir));
+    let first = input_paths
+        .first()
+        .ok_or_else(|| ConvertError::InvalidPath("No tracks to mix".to_string()))?;
+    let filename = PathBuf::from(first)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .map(|stem| format!("{}-mix", stem))
+        .ok_or_else(|| ConvertError::InvalidPath(format!("Invalid filename: {}", first)))?;
     
-    let output_path = temp_dir.path().join(format!("{}.m4a", filename));
+    // The mix lasts as long as the longest track, which progress is measured against
+    let mut duration_ms = None;
+    for path in &input_paths {
+        let media = inspect_input(&app, path).await?;
+        duration_ms = duration_ms.max(media.duration_ms);
+    }
+    
+    let routing = AudioRouting {
+        input_args: input_paths
+            .iter()
+            .flat_map(|path| ["-i".to_string(), path.clone()])
+            .collect(),
+        map_args: mix_filter_args(input_paths.len()),
+        duration_ms,
+    };
+    let (temp_dir, output_path, chunks) = convert_in_workspace(&app, &filename, &routing, None, job_id.as_deref()).await?;
+    Ok(ConversionResult {
+        output_path,
+        temp_dir,
+        chunks,
+        timeline: None,
+        files: Vec::new(),
+    })
+}
+
+// FFmpeg arguments choosing which audio of the input ends up in the output
+struct AudioRouting {
+    /// Inputs with their options (-ss / -t / -i)
+    input_args: Vec<String>,
+    /// Stream mapping and filters, after the inputs
+    map_args: Vec<String>,
+    /// Length of the output, when it isn't the first input's
+    duration_ms: Option<i64>,
+}
+
+// Convert into a new temp directory, which is removed again if conversion fails.
+// Returns the directory, the output path and any chunks.
+async fn convert_in_workspace(
+    app: &AppHandle,
+    filename: &str,
+    routing: &AudioRouting,
+    split: Option<&SplitOptions>,
+    job_id: Option<&str>,
+) -> Result<(String, String, Vec<AudioChunk>), ConvertError> {
+    // Create a temp directory for the output; it lives until cleanup_temp_dir or app exit
+    let workspaces = app.state::<TempWorkspaces>();
+    let temp_dir = workspaces.create()?;
+    let temp_dir_path = temp_dir.to_string_lossy().to_string();
+    
+    info!("Created temp directory: {}", temp_dir_path);
+    
+    let result = convert_into(app, filename, &temp_dir, routing, split, job_id).await;
+    if result.is_err() {
+        if let Err(e) = workspaces.release(&temp_dir) {
+            warn!("Failed to remove temp dir {}: {}", temp_dir_path, e);
+        }
+    }
+    let (output_str, chunks) = result?;
+    Ok((temp_dir_path, output_str, chunks))
+}
+
+// Convert the input into `temp_dir`, returning the output path and any chunks
+async fn convert_into(
+    app: &AppHandle,
+    filename: &str,
+    temp_dir: &Path,
+    routing: &AudioRouting,
+    split: Option<&SplitOptions>,
+    job_id: Option<&str>,
+) -> Result<(String, Vec<AudioChunk>), ConvertError> {
+    let output_path = temp_dir.join(format!("{}.m4a", filename));
     let output_str = output_path.to_string_lossy().to_string();
     
     info!("Output path: {}", output_str);
     
-    // Get the shell plugin to run FFmpeg
-    let shell = app.shell();
-    
     info!("Running FFmpeg...");
     
     // Build FFmpeg command arguments:
-    // -i {input}    Input file
+    // (inputs)      -i {input} for each input, after -ss / -t to read only the requested ranges
+    // (routing)     -map / -af / -filter_complex for the chosen audio, if any
     // -vn           Strip video track
     // -ac 1         Mono channel
     // -ar 16000     16kHz sample rate
     // -c:a aac      Use AAC codec (better seeking than mp3 at low bitrates)
     // -b:a 32k      32kbps bitrate
     // -y            Overwrite output without asking
-    let output = shell
-        .sidecar("ffmpeg")
-        .map_err(|e| {
-            error!("Failed to create FFmpeg sidecar: {}", e);
-            ConvertError::FfmpegFailed(format!("Failed to start FFmpeg: {}", e))
-        })?
-        .args([
-            "-i", &input_path,
-            "-vn",
-            "-ac", "1",
-            "-ar", "16000",
-            "-c:a", "aac",
-            "-b:a", "32k",
-            "-y",
-            &output_str,
-        ])
-        .output()
-        .await
-        .map_err(|e| {
-            error!("FFmpeg execution failed: {}", e);
-            ConvertError::FfmpegFailed(format!("FFmpeg failed to execute: {}", e))
-        })?;
-    
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        error!("FFmpeg exited with code {:?}", output.status.code());
-        error!("FFmpeg stderr: {}", stderr);
-        error!("FFmpeg stdout: {}", stdout);
-        return Err(ConvertError::FfmpegFailed(format!(
-            "FFmpeg exited with code {:?}: {}",
-            output.status.code(),
-            stderr
-        )));
-    }
+    let mut args: Vec<&str> = routing.input_args.iter().map(String::as_str).collect();
+    args.extend(routing.map_args.iter().map(String::as_str));
+    args.extend([
+        "-vn",
+        "-ac", "1",
+        "-ar", "16000",
+        "-c:a", "aac",
+        "-b:a", "32k",
+        "-y",
+        &output_str,
+    ]);
+    run_ffmpeg_with_progress(app, &args, job_id, routing.duration_ms).await?;
     
     // Verify output file was created
     if !output_path.exists() {
@@ -136,20 +991,33 @@ pub async fn convert_to_audio(
     
     info!("Conversion successful! Output size: {} bytes", output_size);
     
-    Ok(ConversionResult {
-        output_path: output_str,
-        temp_dir: temp_dir_path,
-    })
+    let chunks = match split {
+        Some(options) => split_audio(app, &output_path, options).await?,
+        None => Vec::new(),
+    };
+    
+    Ok((output_str, chunks))
+}
+
+/// The audio cleanup profiles conversion can apply
+#[tauri::command]
+pub async fn list_cleanup_profiles() -> Vec<CleanupProfileInfo> {
+    CleanupProfile::ALL
+        .iter()
+        .map(|&profile| CleanupProfileInfo {
+            id: profile,
+            label: profile.label(),
+            filters: profile.filters().to_vec(),
+        })
+        .collect()
 }
 
-/// Clean up a temporary directory after transcription is complete
+/// Clean up a temporary directory after transcription is complete.
+/// Only directories created by `convert_to_audio` are removed.
 #[tauri::command]
-pub async fn cleanup_temp_dir(temp_dir: String) -> Result<(), String> {
+pub async fn cleanup_temp_dir(app: AppHandle, temp_dir: String) -> Result<(), TempWorkspaceError> {
     info!("Cleaning up temp dir: {}", temp_dir);
-    let path = PathBuf::from(&temp_dir);
-    if path.exists() && path.is_dir() {
-        std::fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
-        info!("Temp dir cleaned up successfully");
-    }
+    app.state::<TempWorkspaces>().release(Path::new(&temp_dir))?;
+    info!("Temp dir cleaned up successfully");
     Ok(())
 }
@@ -0,0 +1,194 @@
+// src-tauri/src/commands/job_queue.rs
+// Durable record of backend transcription jobs so they survive app restarts
+
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri::Manager;
+use thiserror::Error;
+
+use super::convert::ConvertOptions;
+use super::timeline::TimelineMap;
+use super::transcribe::{JobStage, TranscriptionOptions};
+
+#[derive(Error, Debug)]
+pub enum JobQueueError {
+    #[error("Failed to access jobs directory: {0}")]
+    DirectoryError(String),
+    #[error("Failed to read job: {0}")]
+    ReadError(String),
+    #[error("Failed to write job: {0}")]
+    WriteError(String),
+    #[error("Job not found: {0}")]
+    NotFound(String),
+    #[error("Job is still running: {0}")]
+    StillRunning(String),
+}
+
+impl serde::Serialize for JobQueueError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Everything needed to pick a job back up after a restart.
+/// The API key is deliberately not stored; resumed jobs use the one in settings.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobRecord {
+    pub job_id: String,
+    pub input_path: String,
+    pub stage: JobStage,
+    pub options: TranscriptionOptions,
+    /// Audio, ranges and cleanup to convert with
+    #[serde(flatten)]
+    pub convert: ConvertOptions,
+    /// Set once converted, so a resumed job can map its transcript back to the source
+    #[serde(default)]
+    pub timeline: Option<TimelineMap>,
+    #[serde(default)]
+    pub upload_url: Option<String>,
+    #[serde(default)]
+    pub transcript_id: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl JobRecord {
+    pub fn new(
+        job_id: String,
+        input_path: String,
+        options: TranscriptionOptions,
+        convert: ConvertOptions,
+    ) -> Self {
+        let now = Utc::now().to_rfc3339();
+        Self {
+            job_id,
+            input_path,
+            stage: JobStage::Queued,
+            options,
+            convert,
+            timeline: None,
+            upload_url: None,
+            transcript_id: None,
+            error: None,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    /// Whether the job still has work left to do
+    pub fn is_unfinished(&self) -> bool {
+        !matches!(self.stage, JobStage::Complete | JobStage::Error)
+    }
+}
+
+// Get the jobs directory path
+fn get_jobs_dir(app: &AppHandle) -> Result<PathBuf, JobQueueError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| JobQueueError::DirectoryError(e.to_string()))?;
+    
+    let jobs_dir = app_data_dir.join("jobs");
+    
+    // Create directory if it doesn't exist
+    if !jobs_dir.exists() {
+        fs::create_dir_all(&jobs_dir)
+            .map_err(|e| JobQueueError::DirectoryError(e.to_string()))?;
+        info!("Created jobs directory: {:?}", jobs_dir);
+    }
+    
+    Ok(jobs_dir)
+}
+
+/// Write a job record to disk, replacing any previous state
+pub fn save_job(app: &AppHandle, record: &mut JobRecord) -> Result<(), JobQueueError> {
+    let jobs_dir = get_jobs_dir(app)?;
+    record.updated_at = Utc::now().to_rfc3339();
+    
+    let content = serde_json::to_string_pretty(record)
+        .map_err(|e| JobQueueError::WriteError(e.to_string()))?;
+    
+    // Write to a temp file first so a crash mid-write can't corrupt the record
+    let file_path = jobs_dir.join(format!("{}.json", record.job_id));
+    let tmp_path = jobs_dir.join(format!("{}.json.tmp", record.job_id));
+    fs::write(&tmp_path, content)
+        .map_err(|e| JobQueueError::WriteError(e.to_string()))?;
+    fs::rename(&tmp_path, &file_path)
+        .map_err(|e| JobQueueError::WriteError(e.to_string()))?;
+    
+    Ok(())
+}
+
+/// Load every job record from disk, oldest first
+pub fn load_jobs(app: &AppHandle) -> Result<Vec<JobRecord>, JobQueueError> {
+    let jobs_dir = get_jobs_dir(app)?;
+    
+    let mut jobs: Vec<JobRecord> = Vec::new();
+    
+    let dir_entries = fs::read_dir(&jobs_dir)
+        .map_err(|e| JobQueueError::ReadError(e.to_string()))?;
+    
+    for dir_entry in dir_entries.flatten() {
+        let path = dir_entry.path();
+        if path.extension().map(|e| e == "json").unwrap_or(false) {
+            match fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|content| serde_json::from_str::<JobRecord>(&content).map_err(|e| e.to_string()))
+            {
+                Ok(record) => jobs.push(record),
+                Err(e) => warn!("Skipping unreadable job record {:?}: {}", path, e),
+            }
+        }
+    }
+    
+    jobs.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(jobs)
+}
+
+/// Remove a job record from disk
+pub fn remove_job(app: &AppHandle, job_id: &str) -> Result<(), JobQueueError> {
+    let jobs_dir = get_jobs_dir(app)?;
+    let file_path = jobs_dir.join(format!("{}.json", job_id));
+    
+    if file_path.exists() {
+        fs::remove_file(&file_path)
+            .map_err(|e| JobQueueError::WriteError(e.to_string()))?;
+        info!("Removed job record: {}", job_id);
+    }
+    
+    Ok(())
+}
+
+/// List persisted jobs (unfinished and failed) so the UI can show them after a restart
+#[tauri::command]
+pub async fn list_transcription_jobs(app: AppHandle) -> Result<Vec<JobRecord>, JobQueueError> {
+    load_jobs(&app)
+}
+
+/// Forget a failed job. Jobs still in progress are refused, since their runner would
+/// write the record back (or a resume would lose it).
+#[tauri::command]
+pub async fn dismiss_transcription_job(app: AppHandle, job_id: String) -> Result<(), JobQueueError> {
+    let file_path = get_jobs_dir(&app)?.join(format!("{}.json", job_id));
+    if !file_path.exists() {
+        return Err(JobQueueError::NotFound(job_id));
+    }
+    
+    let record: JobRecord = fs::read_to_string(&file_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        .map_err(JobQueueError::ReadError)?;
+    if record.stage != JobStage::Error {
+        return Err(JobQueueError::StillRunning(job_id));
+    }
+    remove_job(&app, &job_id)
+}
@@ -5,8 +5,18 @@ use log::info;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+use tauri::AppHandle;
 use thiserror::Error;
 
+use super::settings::load_api_endpoints;
+use crate::http::{send_with_retry, shared_client, RetryPolicy};
+
+/// LeMUR answers can take a while to generate; retry a few times on rate limits. Each
+/// request is billed, so one that may have reached LeMUR isn't sent again.
+pub const LEMUR_RETRY: RetryPolicy =
+    RetryPolicy::new(3, Duration::from_secs(2), Duration::from_secs(30)).non_idempotent();
+
 #[derive(Error, Debug)]
 pub enum LemurError {
     #[error("API request failed: {0}")]
@@ -64,14 +74,27 @@ struct LemurErrorResponse {
 /// of speaker labels (A, B, C...) to inferred names.
 #[tauri::command]
 pub async fn identify_speakers(
+    app: AppHandle,
     transcript_text: String,
     speaker_labels: Vec<String>,
     api_key: String,
+) -> Result<HashMap<String, String>, LemurError> {
+    let lemur_base = load_api_endpoints(&app).lemur_v3();
+    request_speaker_names(&shared_client(&app), &lemur_base, &LEMUR_RETRY, transcript_text, speaker_labels, &api_key).await
+}
+
+/// Ask LeMUR for speaker names against the given LeMUR root
+/// (e.g. https://api.assemblyai.com/lemur/v3)
+pub async fn request_speaker_names(
+    client: &Client,
+    lemur_base: &str,
+    retry: &RetryPolicy,
+    transcript_text: String,
+    speaker_labels: Vec<String>,
+    api_key: &str,
 ) -> Result<HashMap<String, String>, LemurError> {
     info!("Identifying speakers for {} unique speakers", speaker_labels.len());
     
-    let client = Client::new();
-    
     // Build questions for each speaker
     let questions: Vec<LemurQuestion> = speaker_labels
         .iter()
@@ -90,14 +113,15 @@ pub async fn identify_speakers(
     
     info!("Sending LeMUR request...");
     
-    let response = client
-        .post("https://api.assemblyai.com/lemur/v3/generate/question-answer")
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| LemurError::RequestFailed(e.to_string()))?;
+    let response = send_with_retry(retry, "LeMUR request", || {
+        client
+            .post(format!("{}/generate/question-answer", lemur_base))
+            .header("Authorization", api_key)
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+    })
+    .await
+    .map_err(|e| LemurError::RequestFailed(e.to_string()))?;
     
     let status = response.status();
     let response_text = response
@@ -0,0 +1,319 @@
+// src-tauri/src/commands/local_transcribe.rs
+// Offline transcription with the bundled whisper.cpp sidecar, for recordings that
+// must not leave the machine
+
+use log::{info, error};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::settings::load_whisper_model_path;
+use crate::providers::{TranscriptResponse, TranscriptStatus, Utterance, Word};
+use crate::temp_workspace::TempWorkspaces;
+
+// whisper.cpp gains little beyond this many threads on typical laptops
+const MAX_WHISPER_THREADS: usize = 8;
+
+#[derive(Error, Debug)]
+pub enum LocalTranscribeError {
+    #[error("No local model selected. Choose a whisper.cpp model file in settings.")]
+    NoModel,
+    #[error("Model file not found: {0}")]
+    ModelNotFound(String),
+    #[error("Invalid file path: {0}")]
+    InvalidPath(String),
+    #[error("Failed to read settings: {0}")]
+    SettingsError(String),
+    #[error("Failed to create temp directory: {0}")]
+    TempDirError(#[from] std::io::Error),
+    #[error("FFmpeg execution failed: {0}")]
+    FfmpegFailed(String),
+    #[error("Local transcription failed: {0}")]
+    WhisperFailed(String),
+}
+
+impl serde::Serialize for LocalTranscribeError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+// whisper.cpp's full JSON output (-ojf)
+#[derive(Debug, Deserialize)]
+struct WhisperOutput {
+    #[serde(default)]
+    result: Option<WhisperResult>,
+    #[serde(default)]
+    transcription: Vec<WhisperSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperResult {
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperSegment {
+    offsets: Offsets,
+    text: String,
+    #[serde(default)]
+    tokens: Vec<WhisperToken>,
+    /// Set by tinydiarize (-tdrz) models when the next segment is a new speaker
+    #[serde(default)]
+    speaker_turn_next: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperToken {
+    text: String,
+    offsets: Offsets,
+    #[serde(default)]
+    p: Option<f64>,
+}
+
+// Milliseconds from the start of the audio
+#[derive(Debug, Deserialize)]
+struct Offsets {
+    from: i64,
+    to: i64,
+}
+
+// Control tokens like [_BEG_], [_TT_150] and [_SOT_] aren't speech
+fn is_control_token(text: &str) -> bool {
+    text.starts_with("[_") && text.ends_with(']')
+}
+
+// Join sub-word tokens into words. A token starting with a space begins a new word;
+// a word's confidence is the mean of its tokens' probabilities.
+fn words_from_tokens(tokens: &[WhisperToken], speaker: &str) -> Vec<Word> {
+    let mut words: Vec<(Word, Vec<f64>)> = Vec::new();
+    
+    for token in tokens.iter().filter(|t| !is_control_token(&t.text)) {
+        let starts_word = token.text.starts_with(' ') || words.is_empty();
+        let text = token.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if starts_word {
+            words.push((
+                Word {
+                    text: text.to_string(),
+                    start: token.offsets.from,
+                    end: token.offsets.to,
+                    confidence: None,
+                    speaker: Some(speaker.to_string()),
+                },
+                token.p.into_iter().collect(),
+            ));
+        } else if let Some((word, probabilities)) = words.last_mut() {
+            word.text.push_str(text);
+            word.end = token.offsets.to;
+            probabilities.extend(token.p);
+        }
+    }
+    
+    words
+        .into_iter()
+        .map(|(mut word, probabilities)| {
+            if !probabilities.is_empty() {
+                word.confidence = Some(probabilities.iter().sum::<f64>() / probabilities.len() as f64);
+            }
+            word
+        })
+        .collect()
+}
+
+/// Convert whisper.cpp's `-ojf` JSON into a completed transcript.
+/// Consecutive segments are merged into one utterance until a speaker turn. Tinydiarize
+/// (-tdrz) models only mark turns, not who is speaking, so turns alternate between A and B;
+/// without one everything is attributed to speaker A.
+pub fn parse_whisper_output(transcript_id: &str, json: &str) -> Result<TranscriptResponse, LocalTranscribeError> {
+    let output: WhisperOutput = serde_json::from_str(json)
+        .map_err(|e| LocalTranscribeError::WhisperFailed(format!("Failed to parse whisper output: {}", e)))?;
+    
+    let mut utterances: Vec<Utterance> = Vec::new();
+    let mut speaker = "A";
+    let mut new_turn = true;
+    
+    for segment in &output.transcription {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let words = words_from_tokens(&segment.tokens, speaker);
+    
+        match utterances.last_mut() {
+            Some(utterance) if !new_turn => {
+                utterance.text.push(' ');
+                utterance.text.push_str(text);
+                utterance.end = segment.offsets.to;
+                utterance.words.extend(words);
+            }
+            _ => utterances.push(Utterance {
+                speaker: speaker.to_string(),
+                text: text.to_string(),
+                start: segment.offsets.from,
+                end: segment.offsets.to,
+                confidence: None,
+                words,
+                source: None,
+            }),
+        }
+    
+        new_turn = segment.speaker_turn_next;
+        if new_turn {
+            speaker = if speaker == "A" { "B" } else { "A" };
+        }
+    }
+    
+    for utterance in &mut utterances {
+        let confidences: Vec<f64> = utterance.words.iter().filter_map(|w| w.confidence).collect();
+        if !confidences.is_empty() {
+            utterance.confidence = Some(confidences.iter().sum::<f64>() / confidences.len() as f64);
+        }
+    }
+    
+    let text = utterances
+        .iter()
+        .map(|u| u.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    
+    Ok(TranscriptResponse {
+        id: transcript_id.to_string(),
+        status: TranscriptStatus::Completed,
+        text: Some(text),
+        utterances: Some(utterances),
+        summary: None,
+        iab_categories_result: None,
+        sentiment_analysis_results: None,
+        language_code: output.result.and_then(|r| r.language),
+        language_confidence: None,
+        chapters: None,
+        entities: None,
+        error: None,
+        extra: HashMap::new(),
+    })
+}
+
+/// Transcribe a file on this machine with whisper.cpp.
+/// Returns the same transcript shape as AssemblyAI, so history and export work unchanged.
+#[tauri::command]
+pub async fn transcribe_locally(
+    app: AppHandle,
+    input_path: String,
+    language_code: Option<String>,
+) -> Result<TranscriptResponse, LocalTranscribeError> {
+    info!("Starting local transcription for: {}", input_path);
+    
+    let model_path = load_whisper_model_path(&app)
+        .map_err(|e| LocalTranscribeError::SettingsError(e.to_string()))?
+        .ok_or(LocalTranscribeError::NoModel)?;
+    if !PathBuf::from(&model_path).is_file() {
+        return Err(LocalTranscribeError::ModelNotFound(model_path));
+    }
+    if !PathBuf::from(&input_path).exists() {
+        return Err(LocalTranscribeError::InvalidPath(format!("File does not exist: {}", input_path)));
+    }
+    
+    // Removed when this function returns
+    let temp_dir = app.state::<TempWorkspaces>().scratch()?;
+    let wav_path = temp_dir.path().join("audio.wav");
+    let wav_str = wav_path.to_string_lossy().to_string();
+    let output_base = temp_dir.path().join("transcript");
+    let output_base_str = output_base.to_string_lossy().to_string();
+    
+    let shell = app.shell();
+    
+    // whisper.cpp only reads 16kHz 16-bit PCM WAV
+    let output = shell
+        .sidecar("ffmpeg")
+        .map_err(|e| LocalTranscribeError::FfmpegFailed(format!("Failed to start FFmpeg: {}", e)))?
+        .args([
+            "-i", &input_path,
+            "-vn",
+            "-ac", "1",
+            "-ar", "16000",
+            "-c:a", "pcm_s16le",
+            "-y",
+            &wav_str,
+        ])
+        .output()
+        .await
+        .map_err(|e| LocalTranscribeError::FfmpegFailed(format!("FFmpeg failed to execute: {}", e)))?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!("FFmpeg stderr: {}", stderr);
+        return Err(LocalTranscribeError::FfmpegFailed(format!(
+            "FFmpeg exited with code {:?}: {}",
+            output.status.code(),
+            stderr
+        )));
+    }
+    
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(MAX_WHISPER_THREADS)
+        .to_string();
+    let language = language_code.unwrap_or_else(|| "auto".to_string());
+    
+    info!("Running whisper.cpp with model {} ({} threads, language {})", model_path, threads, language);
+    
+    // Build whisper.cpp command arguments:
+    // -m {model}    ggml model file
+    // -f {wav}      Input audio
+    // -l {lang}     Language code, or "auto" to detect
+    // -t {n}        CPU threads
+    // -ojf          Full JSON output (per-token timings and probabilities)
+    // -of {base}    Output path without extension (writes {base}.json)
+    // -np           No progress/info prints
+    let output = shell
+        .sidecar("whisper-cli")
+        .map_err(|e| {
+            error!("Failed to create whisper.cpp sidecar: {}", e);
+            LocalTranscribeError::WhisperFailed(format!("Local engine is not available: {}", e))
+        })?
+        .args([
+            "-m", &model_path,
+            "-f", &wav_str,
+            "-l", &language,
+            "-t", &threads,
+            "-ojf",
+            "-of", &output_base_str,
+            "-np",
+        ])
+        .output()
+        .await
+        .map_err(|e| LocalTranscribeError::WhisperFailed(format!("whisper.cpp failed to execute: {}", e)))?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        error!("whisper.cpp stderr: {}", stderr);
+        return Err(LocalTranscribeError::WhisperFailed(format!(
+            "whisper.cpp exited with code {:?}: {}",
+            output.status.code(),
+            stderr
+        )));
+    }
+    
+    let json = std::fs::read_to_string(output_base.with_extension("json"))
+        .map_err(|e| LocalTranscribeError::WhisperFailed(format!("No transcript was written: {}", e)))?;
+    let transcript_id = format!("local-{}", Uuid::new_v4());
+    let response = parse_whisper_output(&transcript_id, &json)?;
+    
+    info!(
+        "Local transcription complete: {} utterance(s)",
+        response.utterances.as_ref().map_or(0, |u| u.len())
+    );
+    Ok(response)
+}
@@ -0,0 +1,290 @@
+// src-tauri/src/commands/media_info.rs
+// Inspect a media file before converting it: duration, container and streams,
+// read from the stream listing FFmpeg prints for its input
+
+use log::{info, error};
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+use thiserror::Error;
+
+use super::convert::parse_duration_line;
+
+#[derive(Error, Debug)]
+pub enum InspectError {
+    #[error("Invalid file path: {0}")]
+    InvalidPath(String),
+    #[error("FFmpeg execution failed: {0}")]
+    FfmpegFailed(String),
+    #[error("Unrecognized media file: {0}")]
+    Unreadable(String),
+}
+
+impl serde::Serialize for InspectError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamKind {
+    Audio,
+    Video,
+    Subtitle,
+    Data,
+    Attachment,
+}
+
+/// One stream of the input. Audio-only and video-only fields are `None` for other kinds.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MediaStream {
+    /// Position in the file, as used by FFmpeg's `-map 0:<index>`
+    pub index: u32,
+    pub kind: StreamKind,
+    pub codec: String,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    /// Marked as the default stream of its kind
+    pub default: bool,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<u32>,
+    pub channel_layout: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+/// What FFmpeg knows about a file without decoding it
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MediaInfo {
+    /// Demuxer name(s), e.g. "mov,mp4,m4a,3gp,3g2,mj2"
+    pub container: String,
+    pub duration_ms: Option<i64>,
+    pub bitrate_kbps: Option<u32>,
+    pub streams: Vec<MediaStream>,
+}
+
+impl MediaInfo {
+    pub fn audio_streams(&self) -> impl Iterator<Item = &MediaStream> {
+        self.streams.iter().filter(|s| s.kind == StreamKind::Audio)
+    }
+
+    pub fn has_audio(&self) -> bool {
+        self.audio_streams().next().is_some()
+    }
+}
+
+// Split on ", " outside parentheses: "yuv420p(tv, bt709), 1920x1080" has two parts
+fn split_top_level(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts
+}
+
+/// Number of channels in an FFmpeg channel layout ("mono", "stereo", "5.1(side)", "3 channels")
+pub fn channel_count(layout: &str) -> Option<u32> {
+    let layout = layout.split('(').next().unwrap_or(layout).trim();
+    if let Some(n) = layout.strip_suffix(" channels") {
+        return n.trim().parse().ok();
+    }
+    match layout {
+        "mono" => Some(1),
+        "stereo" | "downmix" => Some(2),
+        "quad" => Some(4),
+        "hexagonal" => Some(6),
+        "octagonal" => Some(8),
+        _ => {
+            // "5.1" is five full-range channels plus one LFE
+            let (main, lfe) = layout.split_once('.')?;
+            Some(main.parse::<u32>().ok()? + lfe.parse::<u32>().ok()?)
+        }
+    }
+}
+
+// "#0:1[0x2](eng): Audio: aac (LC), 48000 Hz, stereo, fltp, 128 kb/s (default)"
+fn parse_stream_line(rest: &str) -> Option<MediaStream> {
+    let (id, description) = rest.split_once(": ")?;
+    let (kind_name, details) = description.split_once(": ").unwrap_or((description, ""));
+    let kind = match kind_name.trim() {
+        "Audio" => StreamKind::Audio,
+        "Video" => StreamKind::Video,
+        "Subtitle" => StreamKind::Subtitle,
+        "Data" => StreamKind::Data,
+        "Attachment" => StreamKind::Attachment,
+        _ => return None,
+    };
+    
+    let (_, position) = id.split_once(':')?;
+    let index = position
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    let language = id
+        .split_once('(')
+        .and_then(|(_, lang)| lang.strip_suffix(')'))
+        .filter(|lang| !lang.is_empty() && *lang != "und")
+        .map(str::to_string);
+    
+    let parts = split_top_level(details);
+    let codec = parts
+        .first()
+        .and_then(|p| p.split_whitespace().next())
+        .unwrap_or("")
+        .to_string();
+    
+    let mut stream = MediaStream {
+        index,
+        kind,
+        codec,
+        language,
+        title: None,
+        default: details.contains("(default)"),
+        sample_rate: None,
+        channels: None,
+        channel_layout: None,
+        width: None,
+        height: None,
+    };
+    
+    match kind {
+        StreamKind::Audio => {
+            // The channel layout follows the sample rate
+            if let Some(pos) = parts.iter().position(|p| p.ends_with(" Hz")) {
+                stream.sample_rate = parts[pos].trim_end_matches(" Hz").trim().parse().ok();
+                if let Some(layout) = parts.get(pos + 1) {
+                    stream.channels = channel_count(layout);
+                    stream.channel_layout = Some(layout.to_string());
+                }
+            }
+        }
+        StreamKind::Video => {
+            // "1920x1080 [SAR 1:1 DAR 16:9]"
+            let size = parts.iter().skip(1).find_map(|p| {
+                let (w, h) = p.split_whitespace().next()?.split_once('x')?;
+                Some((w.parse().ok()?, h.parse().ok()?))
+            });
+            if let Some((width, height)) = size {
+                stream.width = Some(width);
+                stream.height = Some(height);
+            }
+        }
+        _ => {}
+    }
+    
+    Some(stream)
+}
+
+// "bitrate: 1200 kb/s" from the Duration line; "N/A" for streams FFmpeg can't measure
+fn parse_bitrate(duration_line: &str) -> Option<u32> {
+    duration_line
+        .split(", ")
+        .find_map(|f| f.strip_prefix("bitrate: "))
+        .and_then(|b| b.trim_end_matches(" kb/s").parse().ok())
+}
+
+/// Read FFmpeg's description of its first input from its log (stderr).
+/// Returns `None` when FFmpeg couldn't open the file as media.
+pub fn parse_media_info(stderr: &str) -> Option<MediaInfo> {
+    let mut info: Option<MediaInfo> = None;
+    let mut in_stream = false;
+    
+    for line in stderr.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("Input #0, ") {
+            // "Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'meeting.mp4':"
+            let container = rest.split(", from '").next().unwrap_or(rest).to_string();
+            info = Some(MediaInfo {
+                container,
+                duration_ms: None,
+                bitrate_kbps: None,
+                streams: Vec::new(),
+            });
+            continue;
+        }
+        // Only the first input is described; stop at outputs or a second input
+        if trimmed.starts_with("Input #") || trimmed.starts_with("Output #") || trimmed.starts_with("Stream mapping") {
+            if info.is_some() {
+                break;
+            }
+            continue;
+        }
+        let Some(info) = info.as_mut() else {
+            continue;
+        };
+    
+        if trimmed.starts_with("Duration: ") {
+            info.duration_ms = parse_duration_line(trimmed);
+            info.bitrate_kbps = parse_bitrate(trimmed);
+        } else if let Some(rest) = trimmed.strip_prefix("Stream ") {
+            let stream = parse_stream_line(rest);
+            in_stream = stream.is_some();
+            info.streams.extend(stream);
+        } else if trimmed.starts_with("Chapter #") {
+            in_stream = false;
+        } else if let Some((key, value)) = trimmed.split_once(':') {
+            // Metadata under a stream line, e.g. "title           : Commentary"
+            if in_stream && key.trim() == "title" {
+                if let Some(stream) = info.streams.last_mut() {
+                    stream.title = Some(value.trim().to_string());
+                }
+            }
+        }
+    }
+    
+    info
+}
+
+/// Describe a media file: duration, container, and its audio, video and subtitle streams
+#[tauri::command]
+pub async fn inspect_media(app: AppHandle, input_path: String) -> Result<MediaInfo, InspectError> {
+    info!("Inspecting media: {}", input_path);
+    
+    if !PathBuf::from(&input_path).exists() {
+        return Err(InspectError::InvalidPath(format!("File does not exist: {}", input_path)));
+    }
+    
+    // With no output file FFmpeg describes the input and exits with an error, which is expected
+    let output = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|e| InspectError::FfmpegFailed(format!("Failed to start FFmpeg: {}", e)))?
+        .args(["-hide_banner", "-i", &input_path])
+        .output()
+        .await
+        .map_err(|e| InspectError::FfmpegFailed(format!("FFmpeg failed to execute: {}", e)))?;
+    
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let media = parse_media_info(&stderr).ok_or_else(|| {
+        error!("FFmpeg could not read {}: {}", input_path, stderr);
+        let reason = stderr.lines().last().unwrap_or("no input description").trim().to_string();
+        InspectError::Unreadable(reason)
+    })?;
+    
+    info!(
+        "{}: {} ms, {} stream(s), container {}",
+        input_path,
+        media.duration_ms.unwrap_or(0),
+        media.streams.len(),
+        media.container
+    );
+    Ok(media)
+}
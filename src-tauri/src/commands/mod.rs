@@ -1,9 +1,19 @@
 pub mod audio;
+pub mod chunked;
 pub mod convert;
 pub mod history;
+pub mod job_queue;
 pub mod lemur;
+pub mod local_transcribe;
+pub mod media_info;
+pub mod multitrack;
 pub mod presets;
+pub mod retention;
+pub mod review;
 pub mod settings;
+pub mod speaker_roles;
+pub mod timeline;
 pub mod transcribe;
 pub mod vocabulary;
 pub mod vocabulary_extract;
+pub mod word_boost;
@@ -0,0 +1,152 @@
+// src-tauri/src/commands/multitrack.rs
+// Transcribing recordings with one track per participant (Zoom's separate audio files,
+// podcast multitracks): each track is transcribed on its own, its speaker named after
+// the track, and the utterances interleaved by time
+
+use log::info;
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use super::chunked::{transcribe_in_parallel, MergedResults};
+use super::timeline::TimelineMap;
+use super::transcribe::{SpeakerLabelMode, TranscribeError, TranscriptionOptions};
+use crate::providers::{TranscriptResponse, Utterance};
+
+/// A pause this long within one track starts a new utterance
+pub const TRACK_PAUSE_MS: i64 = 1500;
+
+/// One participant's converted audio
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpeakerTrack {
+    /// Speaker name for everything said on this track
+    pub name: String,
+    pub path: String,
+    /// Set when the track was trimmed or had silences cut while converting
+    #[serde(default)]
+    pub timeline: Option<TimelineMap>,
+}
+
+/// A finished transcript of one track
+#[derive(Debug)]
+pub struct TrackTranscript {
+    pub name: String,
+    pub timeline: Option<TimelineMap>,
+    pub response: TranscriptResponse,
+}
+
+/// Break an utterance wherever its words pause for `pause_ms` or more. With only one
+/// speaker per track the transcript has no speaker changes to break at, so this is what
+/// lets other tracks' replies fall in between.
+pub fn split_at_pauses(utterance: Utterance, pause_ms: i64) -> Vec<Utterance> {
+    let breaks = utterance.words.windows(2).filter(|w| w[1].start - w[0].end >= pause_ms).count();
+    if breaks == 0 {
+        return vec![utterance];
+    }
+    
+    let mut groups = vec![Vec::new()];
+    let mut previous_end = None;
+    for word in utterance.words {
+        if previous_end.is_some_and(|end| word.start - end >= pause_ms) {
+            groups.push(Vec::new());
+        }
+        previous_end = Some(word.end);
+        groups.last_mut().unwrap().push(word);
+    }
+    
+    groups
+        .into_iter()
+        .map(|words| {
+            let confidences: Vec<f64> = words.iter().filter_map(|w| w.confidence).collect();
+            Utterance {
+                speaker: utterance.speaker.clone(),
+                text: words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" "),
+                start: words[0].start,
+                end: words[words.len() - 1].end,
+                confidence: (!confidences.is_empty())
+                    .then(|| confidences.iter().sum::<f64>() / confidences.len() as f64),
+                words,
+                source: None,
+            }
+        })
+        .collect()
+}
+
+/// Combine per-track transcripts into one: every utterance is credited to its track's
+/// speaker, moved back onto the recording's timeline, and ordered by start time.
+/// The result lists the transcript of every track in `track_transcript_ids`.
+pub fn interleave_tracks(tracks: Vec<TrackTranscript>) -> TranscriptResponse {
+    let mut utterances: Vec<Utterance> = Vec::new();
+    let mut merged = MergedResults::default();
+    
+    for track in tracks {
+        let mut response = track.response;
+        if let Some(timeline) = &track.timeline {
+            timeline.remap_transcript(&mut response);
+        }
+    
+        for utterance in response.utterances.take().unwrap_or_default() {
+            for mut part in split_at_pauses(utterance, TRACK_PAUSE_MS) {
+                part.speaker = track.name.clone();
+                for word in &mut part.words {
+                    word.speaker = Some(track.name.clone());
+                }
+                utterances.push(part);
+            }
+        }
+        for sentiment in response.sentiment_analysis_results.iter_mut().flatten() {
+            sentiment.speaker = Some(track.name.clone());
+        }
+        // Each summary only covers what its participant said
+        response.summary = response
+            .summary
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| format!("{}:\n{}", track.name, s));
+        merged.add(response);
+    }
+
+    // Stable sort keeps track order for anything starting at the same moment
+    utterances.sort_by_key(|u| u.start);
+    merged.finish(utterances, "track_transcript_ids")
+}
+
+/// Transcribe one track per participant in parallel and interleave them into one
+/// transcript with each utterance credited to its track. Speakers aren't detected: each
+/// track is transcribed as a single speaker, so `speaker_label_mode` is ignored.
+/// `cancel_transcription_wait(job_id)` stops every track, and the first track to fail
+/// stops the rest.
+#[tauri::command]
+pub async fn transcribe_tracks(
+    app: AppHandle,
+    tracks: Vec<SpeakerTrack>,
+    api_key: String,
+    options: TranscriptionOptions,
+    job_id: String,
+) -> Result<TranscriptResponse, TranscribeError> {
+    info!("Transcribing {} speaker tracks for job {}", tracks.len(), job_id);
+    
+    let options = TranscriptionOptions {
+        max_speakers: Some(1),
+        speaker_label_mode: SpeakerLabelMode::Generic,
+        speaker_values: Vec::new(),
+        role_set_id: None,
+        ..options
+    };
+    let paths: Vec<String> = tracks.iter().map(|t| t.path.clone()).collect();
+    let responses = transcribe_in_parallel(&app, &paths, api_key, &options, &job_id).await?;
+    let track_transcripts = tracks
+        .into_iter()
+        .zip(responses)
+        .map(|(track, response)| TrackTranscript {
+            name: track.name,
+            timeline: track.timeline,
+            response,
+        })
+        .collect();
+    let merged = interleave_tracks(track_transcripts);
+    info!(
+        "Interleaved speaker tracks for job {} ({} utterances)",
+        job_id,
+        merged.utterances.as_ref().map_or(0, |u| u.len())
+    );
+    Ok(merged)
+}
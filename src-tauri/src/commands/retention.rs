@@ -0,0 +1,161 @@
+// src-tauri/src/commands/retention.rs
+// Deleting transcripts (and their uploaded audio) from the transcription provider,
+// with a local log of every confirmed remote deletion
+
+use chrono::Utc;
+use log::{info, error};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri::Manager;
+use thiserror::Error;
+
+use super::transcribe::assemblyai_provider;
+use crate::http::RetryPolicy;
+use crate::providers::TranscriptionProvider;
+
+// Deletions are compliance-critical, so be patient with rate limits and outages
+pub(crate) const DELETE_RETRY: RetryPolicy = RetryPolicy::new(4, Duration::from_secs(2), Duration::from_secs(30));
+
+#[derive(Error, Debug)]
+pub enum RetentionError {
+    #[error("Failed to access app data directory: {0}")]
+    DirectoryError(String),
+    #[error("Failed to read deletion log: {0}")]
+    ReadError(String),
+    #[error("Failed to write deletion log: {0}")]
+    WriteError(String),
+    #[error("Remote deletion failed: {0}")]
+    DeleteFailed(String),
+}
+
+impl serde::Serialize for RetentionError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Serializes appends to the deletion log, which auto-deletes from finished jobs and
+/// deletions from the UI can make at the same time
+#[derive(Default)]
+pub struct DeletionLogLock(Mutex<()>);
+
+/// A transcript the provider confirmed it deleted
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteDeletion {
+    pub transcript_id: String,
+    pub provider: String,
+    /// Local history entry the transcript was saved as, if known
+    #[serde(default)]
+    pub history_id: Option<String>,
+    pub deleted_at: String,
+}
+
+// Get the deletion log path
+fn get_deletions_file(app: &AppHandle) -> Result<PathBuf, RetentionError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| RetentionError::DirectoryError(e.to_string()))?;
+    
+    // Create directory if it doesn't exist
+    if !app_data_dir.exists() {
+        fs::create_dir_all(&app_data_dir)
+            .map_err(|e| RetentionError::DirectoryError(e.to_string()))?;
+        info!("Created app data directory: {:?}", app_data_dir);
+    }
+    
+    Ok(app_data_dir.join("remote_deletions.json"))
+}
+
+/// Load the deletion log, oldest first
+pub fn load_remote_deletions(app: &AppHandle) -> Result<Vec<RemoteDeletion>, RetentionError> {
+    let deletions_file = get_deletions_file(app)?;
+    
+    if !deletions_file.exists() {
+        return Ok(Vec::new());
+    }
+    
+    let content = fs::read_to_string(&deletions_file)
+        .map_err(|e| RetentionError::ReadError(e.to_string()))?;
+    
+    serde_json::from_str(&content)
+        .map_err(|e| RetentionError::ReadError(e.to_string()))
+}
+
+// Append a deletion to the log
+fn record_remote_deletion(app: &AppHandle, deletion: RemoteDeletion) -> Result<(), RetentionError> {
+    let lock = app.try_state::<DeletionLogLock>();
+    let _guard = lock.as_ref().map(|l| l.0.lock().unwrap());
+    
+    let deletions_file = get_deletions_file(app)?;
+    let mut deletions = load_remote_deletions(app)?;
+    deletions.push(deletion);
+    
+    let content = serde_json::to_string_pretty(&deletions)
+        .map_err(|e| RetentionError::WriteError(e.to_string()))?;
+    
+    // Write to a temp file first so a crash mid-write can't lose earlier records
+    let tmp_file = deletions_file.with_extension("json.tmp");
+    fs::write(&tmp_file, content)
+        .map_err(|e| RetentionError::WriteError(e.to_string()))?;
+    fs::rename(&tmp_file, &deletions_file)
+        .map_err(|e| RetentionError::WriteError(e.to_string()))?;
+    
+    Ok(())
+}
+
+/// Delete a transcript from the provider and log the confirmed deletion.
+/// For AssemblyAI this also removes the uploaded audio the transcript was made from.
+pub async fn delete_and_record(
+    app: &AppHandle,
+    provider: &dyn TranscriptionProvider,
+    transcript_id: &str,
+    history_id: Option<String>,
+) -> Result<RemoteDeletion, RetentionError> {
+    provider
+        .delete(transcript_id)
+        .await
+        .map_err(|e| RetentionError::DeleteFailed(e.to_string()))?;
+    
+    let deletion = RemoteDeletion {
+        transcript_id: transcript_id.to_string(),
+        provider: provider.name().to_string(),
+        history_id,
+        deleted_at: Utc::now().to_rfc3339(),
+    };
+    
+    // The remote data is already gone; a failed log write must not look like a failed delete
+    if let Err(e) = record_remote_deletion(app, deletion.clone()) {
+        error!("Transcript {} was deleted but the deletion could not be logged: {}", transcript_id, e);
+    }
+    
+    info!("Remote transcript {} deleted from {}", transcript_id, deletion.provider);
+    Ok(deletion)
+}
+
+/// Delete a transcript (and its uploaded audio) from AssemblyAI
+#[tauri::command]
+pub async fn delete_remote_transcript(
+    app: AppHandle,
+    transcript_id: String,
+    api_key: String,
+    history_id: Option<String>,
+) -> Result<RemoteDeletion, RetentionError> {
+    info!("Deleting remote transcript: {}", transcript_id);
+    
+    let provider = assemblyai_provider(&app, api_key, DELETE_RETRY);
+    delete_and_record(&app, &provider, &transcript_id, history_id).await
+}
+
+/// List every confirmed remote deletion
+#[tauri::command]
+pub async fn list_remote_deletions(app: AppHandle) -> Result<Vec<RemoteDeletion>, RetentionError> {
+    load_remote_deletions(&app)
+}
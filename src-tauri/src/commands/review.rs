@@ -0,0 +1,139 @@
+// src-tauri/src/commands/review.rs
+// Points editors at the passages of a transcript the model was least sure of,
+// so they only need to check those against the audio
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use thiserror::Error;
+
+use super::history::get_history_entry;
+use crate::providers::Utterance;
+
+/// Words below this confidence are flagged when no threshold is given
+pub const DEFAULT_CONFIDENCE_THRESHOLD: f64 = 0.7;
+
+#[derive(Error, Debug)]
+pub enum ReviewError {
+    #[error("History entry not found: {0}")]
+    NotFound(String),
+    #[error("Failed to read history entry: {0}")]
+    ReadError(String),
+    #[error("Confidence threshold must be between 0 and 1, got {0}")]
+    InvalidThreshold(f64),
+}
+
+impl serde::Serialize for ReviewError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// A run of consecutive low-confidence words within one speaker turn
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct LowConfidenceSpan {
+    pub speaker: String,
+    pub start: i64,
+    pub end: i64,
+    pub text: String,
+    /// Lowest word confidence in the span
+    pub confidence: f64,
+    pub word_count: usize,
+}
+
+// Only the parts of a saved history entry needed for review
+#[derive(Deserialize)]
+struct HistoryTranscript {
+    transcript: SegmentList,
+}
+
+#[derive(Deserialize)]
+struct SegmentList {
+    #[serde(default)]
+    segments: Vec<Utterance>,
+}
+
+/// Collect the runs of words whose confidence is below `threshold`, in transcript order.
+/// Segments saved without word timings fall back to the segment's own confidence.
+pub fn find_low_confidence_spans(segments: &[Utterance], threshold: f64) -> Vec<LowConfidenceSpan> {
+    let mut spans = Vec::new();
+    
+    for segment in segments {
+        if segment.words.is_empty() {
+            if let Some(confidence) = segment.confidence.filter(|c| *c < threshold) {
+                spans.push(LowConfidenceSpan {
+                    speaker: segment.speaker.clone(),
+                    start: segment.start,
+                    end: segment.end,
+                    text: segment.text.clone(),
+                    confidence,
+                    word_count: segment.text.split_whitespace().count(),
+                });
+            }
+            continue;
+        }
+    
+        let mut current: Option<LowConfidenceSpan> = None;
+        for word in &segment.words {
+            // Words without a confidence can't be judged, so they end a run
+            let Some(confidence) = word.confidence.filter(|c| *c < threshold) else {
+                spans.extend(current.take());
+                continue;
+            };
+            match current.as_mut() {
+                Some(span) => {
+                    span.end = word.end;
+                    span.text.push(' ');
+                    span.text.push_str(&word.text);
+                    span.confidence = span.confidence.min(confidence);
+                    span.word_count += 1;
+                }
+                None => {
+                    current = Some(LowConfidenceSpan {
+                        speaker: segment.speaker.clone(),
+                        start: word.start,
+                        end: word.end,
+                        text: word.text.clone(),
+                        confidence,
+                        word_count: 1,
+                    });
+                }
+            }
+        }
+        spans.extend(current);
+    }
+    
+    spans
+}
+
+/// List the low-confidence passages of a saved transcript
+#[tauri::command]
+pub async fn get_low_confidence_spans(
+    app: AppHandle,
+    history_id: String,
+    threshold: Option<f64>,
+) -> Result<Vec<LowConfidenceSpan>, ReviewError> {
+    let threshold = threshold.unwrap_or(DEFAULT_CONFIDENCE_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(ReviewError::InvalidThreshold(threshold));
+    }
+    
+    let content = get_history_entry(app, history_id.clone())
+        .await
+        .map_err(|e| ReviewError::ReadError(e.to_string()))?
+        .ok_or_else(|| ReviewError::NotFound(history_id.clone()))?;
+    let entry: HistoryTranscript = serde_json::from_str(&content)
+        .map_err(|e| ReviewError::ReadError(e.to_string()))?;
+    
+    let spans = find_low_confidence_spans(&entry.transcript.segments, threshold);
+    info!(
+        "Found {} low-confidence span(s) below {} in {}",
+        spans.len(),
+        threshold,
+        history_id
+    );
+    Ok(spans)
+}
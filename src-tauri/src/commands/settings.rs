@@ -9,6 +9,8 @@ use tauri::Manager;
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
 
+use crate::http::{build_client, HttpClient};
+
 #[derive(Error, Debug)]
 pub enum SettingsError {
     #[error("Failed to access settings directory: {0}")]
@@ -17,6 +19,10 @@ pub enum SettingsError {
     ReadError(String),
     #[error("Failed to write settings: {0}")]
     WriteError(String),
+    #[error("{0}")]
+    InvalidNetwork(String),
+    #[error("Model file not found: {0}")]
+    ModelNotFound(String),
 }
 
 impl serde::Serialize for SettingsError {
@@ -28,10 +34,86 @@ impl serde::Serialize for SettingsError {
     }
 }
 
+/// Default root URL for AssemblyAI (transcripts under /v2, LeMUR under /lemur/v3)
+pub const DEFAULT_ASSEMBLYAI_BASE_URL: &str = "https://api.assemblyai.com";
+/// Default root URL for OpenAI (chat completions under /v1)
+pub const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com";
+
+// Environment variables that override the configured base URLs (handy for local mocks)
+const ASSEMBLYAI_BASE_URL_ENV: &str = "OHG_ASSEMBLYAI_BASE_URL";
+const OPENAI_BASE_URL_ENV: &str = "OHG_OPENAI_BASE_URL";
+
 #[derive(Serialize, Deserialize, Default)]
 struct AppSettings {
     api_key: Option<String>,
     openai_key: Option<String>,
+    #[serde(default)]
+    assemblyai_base_url: Option<String>,
+    #[serde(default)]
+    openai_base_url: Option<String>,
+    #[serde(default)]
+    network: NetworkSettings,
+    #[serde(default)]
+    auto_delete_remote: bool,
+    #[serde(default)]
+    whisper_model_path: Option<String>,
+}
+
+/// Outbound network configuration for the shared HTTP client
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// HTTP(S) proxy for all outbound requests, e.g. http://proxy.corp:8080
+    pub proxy_url: Option<String>,
+    pub proxy_username: Option<String>,
+    pub proxy_password: Option<String>,
+    /// PEM files with extra root certificates to trust (e.g. a corporate CA)
+    pub ca_certificates: Vec<String>,
+    pub connect_timeout_secs: Option<u64>,
+    /// Maximum time to wait between reads of a response
+    pub read_timeout_secs: Option<u64>,
+}
+
+/// Root URLs of the external APIs the backend talks to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiEndpoints {
+    pub assemblyai: String,
+    pub openai: String,
+}
+
+impl Default for ApiEndpoints {
+    fn default() -> Self {
+        Self {
+            assemblyai: DEFAULT_ASSEMBLYAI_BASE_URL.to_string(),
+            openai: DEFAULT_OPENAI_BASE_URL.to_string(),
+        }
+    }
+}
+
+impl ApiEndpoints {
+    /// AssemblyAI transcript API root, e.g. https://api.assemblyai.com/v2
+    pub fn assemblyai_v2(&self) -> String {
+        format!("{}/v2", self.assemblyai.trim_end_matches('/'))
+    }
+
+    /// AssemblyAI LeMUR API root, e.g. https://api.assemblyai.com/lemur/v3
+    pub fn lemur_v3(&self) -> String {
+        format!("{}/lemur/v3", self.assemblyai.trim_end_matches('/'))
+    }
+
+    /// OpenAI API root, e.g. https://api.openai.com/v1
+    pub fn openai_v1(&self) -> String {
+        format!("{}/v1", self.openai.trim_end_matches('/'))
+    }
+}
+
+// Environment variable wins, then settings, then the public default
+fn resolve_base_url(env_var: &str, configured: Option<String>, default: &str) -> String {
+    std::env::var(env_var)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .or(configured.filter(|v| !v.trim().is_empty()))
+        .unwrap_or_else(|| default.to_string())
 }
 
 // Get the settings file path
@@ -79,6 +161,143 @@ fn save_settings(app: &AppHandle, settings: &AppSettings) -> Result<(), Settings
     Ok(())
 }
 
+/// Read the stored AssemblyAI API key for backend use (e.g. resuming jobs at startup)
+pub fn load_api_key(app: &AppHandle) -> Result<Option<String>, SettingsError> {
+    Ok(load_settings(app)?.api_key)
+}
+
+/// Resolve the API base URLs for backend use
+/// Falls back to the defaults if settings can't be read
+pub fn load_api_endpoints(app: &AppHandle) -> ApiEndpoints {
+    let settings = load_settings(app).unwrap_or_else(|e| {
+        error!("Failed to load settings, using default endpoints: {}", e);
+        AppSettings::default()
+    });
+    
+    ApiEndpoints {
+        assemblyai: resolve_base_url(ASSEMBLYAI_BASE_URL_ENV, settings.assemblyai_base_url, DEFAULT_ASSEMBLYAI_BASE_URL),
+        openai: resolve_base_url(OPENAI_BASE_URL_ENV, settings.openai_base_url, DEFAULT_OPENAI_BASE_URL),
+    }
+}
+
+/// Read the network settings for building the HTTP client
+/// Falls back to the defaults if settings can't be read
+pub fn load_network_settings(app: &AppHandle) -> NetworkSettings {
+    load_settings(app)
+        .map(|settings| settings.network)
+        .unwrap_or_else(|e| {
+            error!("Failed to load settings, using default network settings: {}", e);
+            NetworkSettings::default()
+        })
+}
+
+/// Get the stored network settings
+#[tauri::command]
+pub async fn get_network_settings(app: AppHandle) -> Result<NetworkSettings, SettingsError> {
+    Ok(load_settings(&app)?.network)
+}
+
+/// Store network settings and rebuild the shared HTTP client with them
+/// Settings that don't produce a working client (bad proxy URL, unreadable CA file) are rejected
+#[tauri::command]
+pub async fn set_network_settings(
+    app: AppHandle,
+    http: tauri::State<'_, HttpClient>,
+    network: NetworkSettings,
+) -> Result<NetworkSettings, SettingsError> {
+    info!(
+        "Saving network settings: proxy={:?}, ca_certificates={:?}, connect_timeout={:?}, read_timeout={:?}",
+        network.proxy_url, network.ca_certificates, network.connect_timeout_secs, network.read_timeout_secs
+    );
+    
+    let client = build_client(&network).map_err(|e| SettingsError::InvalidNetwork(e.to_string()))?;
+    
+    let mut settings = load_settings(&app)?;
+    settings.network = network.clone();
+    save_settings(&app, &settings)?;
+    http.replace(client);
+    
+    info!("Network settings saved, HTTP client rebuilt");
+    Ok(network)
+}
+
+/// Whether transcripts should be deleted from the provider once saved to local history
+/// Treated as off if settings can't be read
+pub fn load_auto_delete_remote(app: &AppHandle) -> bool {
+    load_settings(app)
+        .map(|settings| settings.auto_delete_remote)
+        .unwrap_or_else(|e| {
+            error!("Failed to load settings, not auto-deleting remote transcripts: {}", e);
+            false
+        })
+}
+
+/// Get whether remote transcripts are deleted automatically after saving
+#[tauri::command]
+pub async fn get_auto_delete_remote(app: AppHandle) -> Result<bool, SettingsError> {
+    Ok(load_settings(&app)?.auto_delete_remote)
+}
+
+/// Turn automatic remote deletion on or off
+#[tauri::command]
+pub async fn set_auto_delete_remote(app: AppHandle, enabled: bool) -> Result<(), SettingsError> {
+    info!("Saving auto-delete remote transcripts: {}", enabled);
+    
+    let mut settings = load_settings(&app)?;
+    settings.auto_delete_remote = enabled;
+    save_settings(&app, &settings)
+}
+
+/// Read the whisper.cpp model file chosen for local transcription
+pub fn load_whisper_model_path(app: &AppHandle) -> Result<Option<String>, SettingsError> {
+    Ok(load_settings(app)?.whisper_model_path)
+}
+
+/// Get the whisper.cpp model file used for local transcription
+#[tauri::command]
+pub async fn get_whisper_model_path(app: AppHandle) -> Result<Option<String>, SettingsError> {
+    load_whisper_model_path(&app)
+}
+
+/// Choose the whisper.cpp model file (ggml .bin) for local transcription, or None to clear it
+#[tauri::command]
+pub async fn set_whisper_model_path(app: AppHandle, path: Option<String>) -> Result<(), SettingsError> {
+    let path = path.filter(|p| !p.trim().is_empty());
+    if let Some(path) = &path {
+        if !PathBuf::from(path).is_file() {
+            return Err(SettingsError::ModelNotFound(path.clone()));
+        }
+    }
+    info!("Saving whisper model path: {:?}", path);
+    
+    let mut settings = load_settings(&app)?;
+    settings.whisper_model_path = path;
+    save_settings(&app, &settings)
+}
+
+/// Get the API base URLs currently in effect
+#[tauri::command]
+pub async fn get_api_endpoints(app: AppHandle) -> Result<ApiEndpoints, SettingsError> {
+    Ok(load_api_endpoints(&app))
+}
+
+/// Store custom API base URLs (None or empty resets to the default)
+#[tauri::command]
+pub async fn set_api_endpoints(
+    app: AppHandle,
+    assemblyai: Option<String>,
+    openai: Option<String>,
+) -> Result<ApiEndpoints, SettingsError> {
+    info!("Saving API endpoints: assemblyai={:?}, openai={:?}", assemblyai, openai);
+    
+    let mut settings = load_settings(&app)?;
+    settings.assemblyai_base_url = assemblyai.filter(|v| !v.trim().is_empty());
+    settings.openai_base_url = openai.filter(|v| !v.trim().is_empty());
+    save_settings(&app, &settings)?;
+    
+    Ok(load_api_endpoints(&app))
+}
+
 /// Get the stored AssemblyAI API key
 #[tauri::command]
 pub async fn get_api_key(app: AppHandle) -> Result<Option<String>, SettingsError> {
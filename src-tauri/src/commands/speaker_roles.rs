@@ -0,0 +1,205 @@
+// src-tauri/src/commands/speaker_roles.rs
+// User-defined role sets (e.g. "Advisory Board: Chair, KOL, Sponsor") for speaker identification
+
+use chrono::Utc;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tauri::AppHandle;
+use tauri::Manager;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::transcribe::{SpeakerLabelMode, TranscriptionOptions};
+
+#[derive(Error, Debug)]
+pub enum RoleSetError {
+    #[error("Failed to access app data directory: {0}")]
+    DirectoryError(String),
+    #[error("Failed to read role sets: {0}")]
+    ReadError(String),
+    #[error("Failed to write role sets: {0}")]
+    WriteError(String),
+    #[error("Role set not found: {0}")]
+    NotFound(String),
+    #[error("Invalid role set: {0}")]
+    Invalid(String),
+}
+
+impl serde::Serialize for RoleSetError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// A named list of roles speakers can be labelled with
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleSet {
+    pub id: String,
+    pub name: String,
+    pub roles: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+// Get the role sets file path
+fn get_role_sets_file(app: &AppHandle) -> Result<PathBuf, RoleSetError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| RoleSetError::DirectoryError(e.to_string()))?;
+    
+    // Create directory if it doesn't exist
+    if !app_data_dir.exists() {
+        fs::create_dir_all(&app_data_dir)
+            .map_err(|e| RoleSetError::DirectoryError(e.to_string()))?;
+        info!("Created app data directory: {:?}", app_data_dir);
+    }
+    
+    Ok(app_data_dir.join("role_sets.json"))
+}
+
+fn load_role_sets(app: &AppHandle) -> Result<Vec<RoleSet>, RoleSetError> {
+    let role_sets_file = get_role_sets_file(app)?;
+    
+    if !role_sets_file.exists() {
+        return Ok(Vec::new());
+    }
+    
+    let content = fs::read_to_string(&role_sets_file)
+        .map_err(|e| RoleSetError::ReadError(e.to_string()))?;
+    
+    serde_json::from_str(&content)
+        .map_err(|e| RoleSetError::ReadError(e.to_string()))
+}
+
+fn save_role_sets(app: &AppHandle, role_sets: &[RoleSet]) -> Result<(), RoleSetError> {
+    let role_sets_file = get_role_sets_file(app)?;
+    
+    let content = serde_json::to_string_pretty(role_sets)
+        .map_err(|e| RoleSetError::WriteError(e.to_string()))?;
+    
+    // Write to a temp file first so a crash mid-write can't lose the saved role sets
+    let tmp_file = role_sets_file.with_extension("json.tmp");
+    fs::write(&tmp_file, content)
+        .map_err(|e| RoleSetError::WriteError(e.to_string()))?;
+    fs::rename(&tmp_file, &role_sets_file)
+        .map_err(|e| RoleSetError::WriteError(e.to_string()))
+}
+
+/// Trim roles, drop blanks and case-insensitive repeats, keeping the first spelling
+pub fn clean_roles(roles: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    roles
+        .into_iter()
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty() && seen.insert(r.to_lowercase()))
+        .collect()
+}
+
+/// Swap a `RoleSet` mode for the saved set's roles; other modes pass through unchanged
+pub fn resolve_role_set(
+    role_sets: &[RoleSet],
+    options: &TranscriptionOptions,
+) -> Result<TranscriptionOptions, RoleSetError> {
+    let mut resolved = options.clone();
+    if options.speaker_label_mode != SpeakerLabelMode::RoleSet {
+        return Ok(resolved);
+    }
+    
+    let id = options
+        .role_set_id
+        .as_deref()
+        .ok_or_else(|| RoleSetError::Invalid("No role set selected".to_string()))?;
+    let role_set = role_sets
+        .iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| RoleSetError::NotFound(id.to_string()))?;
+    
+    resolved.speaker_values = role_set.roles.clone();
+    Ok(resolved)
+}
+
+/// Resolve the selected role set from disk for a submission
+pub fn apply_role_set(app: &AppHandle, options: &TranscriptionOptions) -> Result<TranscriptionOptions, RoleSetError> {
+    if options.speaker_label_mode != SpeakerLabelMode::RoleSet {
+        return Ok(options.clone());
+    }
+    resolve_role_set(&load_role_sets(app)?, options)
+}
+
+/// List the saved role sets
+#[tauri::command]
+pub async fn list_role_sets(app: AppHandle) -> Result<Vec<RoleSet>, RoleSetError> {
+    load_role_sets(&app)
+}
+
+/// Create a role set, or update it when `id` is given
+#[tauri::command]
+pub async fn save_role_set(
+    app: AppHandle,
+    id: Option<String>,
+    name: String,
+    roles: Vec<String>,
+) -> Result<RoleSet, RoleSetError> {
+    let name = name.trim().to_string();
+    let roles = clean_roles(roles);
+    if name.is_empty() {
+        return Err(RoleSetError::Invalid("Name is required".to_string()));
+    }
+    if roles.is_empty() {
+        return Err(RoleSetError::Invalid("At least one role is required".to_string()));
+    }
+    
+    let mut role_sets = load_role_sets(&app)?;
+    let now = Utc::now().to_rfc3339();
+    
+    let role_set = match id {
+        Some(id) => {
+            let existing = role_sets
+                .iter_mut()
+                .find(|s| s.id == id)
+                .ok_or(RoleSetError::NotFound(id))?;
+            existing.name = name;
+            existing.roles = roles;
+            existing.updated_at = now;
+            existing.clone()
+        }
+        None => {
+            let role_set = RoleSet {
+                id: Uuid::new_v4().to_string(),
+                name,
+                roles,
+                created_at: now.clone(),
+                updated_at: now,
+            };
+            role_sets.push(role_set.clone());
+            role_set
+        }
+    };
+    
+    save_role_sets(&app, &role_sets)?;
+    info!("Saved role set: {} ({})", role_set.name, role_set.id);
+    Ok(role_set)
+}
+
+/// Delete a role set
+#[tauri::command]
+pub async fn delete_role_set(app: AppHandle, id: String) -> Result<(), RoleSetError> {
+    let mut role_sets = load_role_sets(&app)?;
+    let before = role_sets.len();
+    role_sets.retain(|s| s.id != id);
+    if role_sets.len() == before {
+        return Err(RoleSetError::NotFound(id));
+    }
+    
+    save_role_sets(&app, &role_sets)?;
+    info!("Deleted role set: {}", id);
+    Ok(())
+}
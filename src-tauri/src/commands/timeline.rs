@@ -0,0 +1,238 @@
+// src-tauri/src/commands/timeline.rs
+// Maps times in converted audio back to the source recording when conversion kept
+// only parts of it, so transcripts line up with the original file
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::providers::{SourcePosition, TranscriptResponse};
+
+#[derive(Error, Debug)]
+pub enum TimelineError {
+    #[error("Invalid time range: {0}")]
+    InvalidRange(String),
+}
+
+impl serde::Serialize for TimelineError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// A stretch of the source recording, in milliseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+/// Check ranges, sort them and merge any that overlap or touch
+pub fn normalize_ranges(ranges: &[TimeRange]) -> Result<Vec<TimeRange>, TimelineError> {
+    let mut sorted = ranges.to_vec();
+    for range in &sorted {
+        if range.start_ms < 0 || range.end_ms <= range.start_ms {
+            return Err(TimelineError::InvalidRange(format!(
+                "{} ms to {} ms",
+                range.start_ms, range.end_ms
+            )));
+        }
+    }
+    sorted.sort_by_key(|r| r.start_ms);
+    
+    let mut merged: Vec<TimeRange> = Vec::new();
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start_ms <= last.end_ms => last.end_ms = last.end_ms.max(range.end_ms),
+            _ => merged.push(range),
+        }
+    }
+    Ok(merged)
+}
+
+/// One stretch of source audio kept in the converted file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineSegment {
+    /// Where the stretch starts in the converted audio
+    pub output_start_ms: i64,
+    /// Where it starts in the source
+    pub source_start_ms: i64,
+    pub duration_ms: i64,
+}
+
+impl TimelineSegment {
+    fn output_end_ms(&self) -> i64 {
+        self.output_start_ms + self.duration_ms
+    }
+}
+
+/// Which parts of the source the converted audio is made of, in order.
+/// An empty map means the converted audio is the whole source.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineMap {
+    pub segments: Vec<TimelineSegment>,
+}
+
+impl TimelineMap {
+    /// The map for audio made by joining `ranges` (already normalized) end to end
+    pub fn from_ranges(ranges: &[TimeRange]) -> Self {
+        let mut output_start_ms = 0;
+        let segments = ranges
+            .iter()
+            .map(|range| {
+                let segment = TimelineSegment {
+                    output_start_ms,
+                    source_start_ms: range.start_ms,
+                    duration_ms: range.end_ms - range.start_ms,
+                };
+                output_start_ms += segment.duration_ms;
+                segment
+            })
+            .collect();
+        Self { segments }
+    }
+
+    /// True when converted times already are source times
+    pub fn is_identity(&self) -> bool {
+        self.segments.iter().all(|s| s.source_start_ms == s.output_start_ms)
+    }
+
+    // The segment an output time falls in. An end time exactly on a boundary belongs
+    // to the segment it ends, a start time to the one it begins.
+    fn segment_at(&self, output_ms: i64, is_end: bool) -> Option<&TimelineSegment> {
+        let index = self.segments.partition_point(|s| {
+            if is_end {
+                s.output_end_ms() < output_ms
+            } else {
+                s.output_end_ms() <= output_ms
+            }
+        });
+        self.segments.get(index).or(self.segments.last())
+    }
+
+    /// Source time of a start time in the converted audio
+    pub fn to_source(&self, output_ms: i64) -> i64 {
+        self.map(output_ms, false)
+    }
+
+    /// Source time of an end time in the converted audio
+    pub fn to_source_end(&self, output_ms: i64) -> i64 {
+        self.map(output_ms, true)
+    }
+
+    fn map(&self, output_ms: i64, is_end: bool) -> i64 {
+        match self.segment_at(output_ms, is_end) {
+            Some(segment) => segment.source_start_ms + (output_ms - segment.output_start_ms),
+            None => output_ms,
+        }
+    }
+
+    /// Time in the converted audio of a source time, if that part of the source was kept
+    pub fn to_output(&self, source_ms: i64) -> Option<i64> {
+        self.segments
+            .iter()
+            .find(|s| source_ms >= s.source_start_ms && source_ms < s.source_start_ms + s.duration_ms)
+            .map(|s| s.output_start_ms + (source_ms - s.source_start_ms))
+    }
+
+    /// Move every timestamp in a transcript of the converted audio onto the source timeline
+    pub fn remap_transcript(&self, response: &mut TranscriptResponse) {
+        if self.is_identity() {
+            return;
+        }
+        let span = |start: &mut i64, end: &mut i64| {
+            *start = self.to_source(*start);
+            *end = self.to_source_end(*end);
+        };
+    
+        for utterance in response.utterances.iter_mut().flatten() {
+            span(&mut utterance.start, &mut utterance.end);
+            for word in &mut utterance.words {
+                span(&mut word.start, &mut word.end);
+            }
+        }
+        for sentiment in response.sentiment_analysis_results.iter_mut().flatten() {
+            span(&mut sentiment.start, &mut sentiment.end);
+        }
+        for chapter in response.chapters.iter_mut().flatten() {
+            span(&mut chapter.start, &mut chapter.end);
+        }
+        for entity in response.entities.iter_mut().flatten() {
+            span(&mut entity.start, &mut entity.end);
+        }
+        // AssemblyAI's flat word list is passed through untyped
+        if let Some(words) = response.extra.get_mut("words").and_then(|w| w.as_array_mut()) {
+            for word in words {
+                if let (Some(start), Some(end)) = (word["start"].as_i64(), word["end"].as_i64()) {
+                    word["start"] = self.to_source(start).into();
+                    word["end"] = self.to_source_end(end).into();
+                }
+            }
+        }
+    }
+}
+
+/// One recording of a session made by joining several files end to end
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionFile {
+    pub path: String,
+    /// Where the file starts in the session
+    pub offset_ms: i64,
+    pub duration_ms: i64,
+}
+
+/// Lay files of the given durations end to end, in order
+pub fn session_files(paths: &[String], durations_ms: &[i64]) -> Vec<SessionFile> {
+    let mut offset_ms = 0;
+    paths
+        .iter()
+        .zip(durations_ms)
+        .map(|(path, &duration_ms)| {
+            let file = SessionFile { path: path.clone(), offset_ms, duration_ms };
+            offset_ms += duration_ms;
+            file
+        })
+        .collect()
+}
+
+/// The file a session time falls in, and the time within it. A time on a boundary
+/// belongs to the file that starts there; times past the end stay in the last file.
+pub fn locate_source(files: &[SessionFile], session_ms: i64) -> Option<SourcePosition> {
+    let index = files
+        .iter()
+        .rposition(|f| f.offset_ms <= session_ms)
+        .or((!files.is_empty()).then_some(0))?;
+    Some(SourcePosition {
+        file: index,
+        local_ms: (session_ms - files[index].offset_ms).max(0),
+    })
+}
+
+/// Note on every utterance of a session transcript which file it was recorded in
+pub fn locate_utterances(files: &[SessionFile], response: &mut TranscriptResponse) {
+    for utterance in response.utterances.iter_mut().flatten() {
+        utterance.source = locate_source(files, utterance.start);
+    }
+}
+
+/// Shift a transcript of trimmed audio back onto the source recording's timeline
+#[tauri::command]
+pub async fn remap_transcript(
+    mut response: TranscriptResponse,
+    timeline: TimelineMap,
+) -> Result<TranscriptResponse, TimelineError> {
+    timeline.remap_transcript(&mut response);
+    Ok(response)
+}
+
+/// Mark each utterance of a joined session's transcript with its source file and local time
+#[tauri::command]
+pub async fn locate_session_sources(
+    mut response: TranscriptResponse,
+    files: Vec<SessionFile>,
+) -> Result<TranscriptResponse, TimelineError> {
+    locate_utterances(&files, &mut response);
+    Ok(response)
+}
@@ -1,11 +1,27 @@
-use log::{info, error};
+use chrono::{SecondsFormat, Utc};
+use log::{info, error, warn};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
 use thiserror::Error;
-use tokio::fs::File;
-use tokio::io::AsyncReadExt;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
-const ASSEMBLYAI_API_BASE: &str = "https://api.assemblyai.com/v2";
+use super::audio::store_audio_file;
+use super::convert::{cleanup_temp_dir, convert_to_audio, ConversionResult, ConvertOptions};
+use super::history::save_history_entry;
+use super::job_queue::{load_jobs, remove_job, save_job, JobRecord};
+use super::retention::{delete_and_record, DELETE_RETRY};
+use super::settings::{load_api_endpoints, load_api_key, load_auto_delete_remote};
+use super::speaker_roles::{apply_role_set, RoleSetError};
+use super::word_boost::{apply_word_boost, record_boost_hits, track_boosted_terms};
+use crate::http::{shared_client, HttpError, RetryPolicy};
+use crate::providers::{AssemblyAiProvider, TranscriptStatus, TranscriptionProvider};
+
+pub use crate::providers::TranscriptResponse;
 
 #[derive(Error, Debug)]
 pub enum TranscribeError {
@@ -19,6 +35,33 @@ pub enum TranscribeError {
     TranscriptionFailed(String),
     #[error("Timeout waiting for transcription")]
     Timeout,
+    #[error("Conversion failed: {0}")]
+    ConversionFailed(String),
+    #[error("Failed to save transcript: {0}")]
+    SaveFailed(String),
+    #[error("Cancelled")]
+    Cancelled,
+    #[error("Invalid transcription options: {0}")]
+    InvalidOptions(String),
+}
+
+impl From<HttpError> for TranscribeError {
+    fn from(err: HttpError) -> Self {
+        match err {
+            HttpError::RequestFailed { .. } => TranscribeError::RequestFailed(err.to_string()),
+            HttpError::RetriesExhausted { .. } => TranscribeError::ApiError(err.to_string()),
+            HttpError::InvalidConfig(_) => TranscribeError::RequestFailed(err.to_string()),
+        }
+    }
+}
+
+impl From<RoleSetError> for TranscribeError {
+    fn from(err: RoleSetError) -> Self {
+        match err {
+            RoleSetError::NotFound(_) | RoleSetError::Invalid(_) => TranscribeError::InvalidOptions(err.to_string()),
+            _ => TranscribeError::FileError(err.to_string()),
+        }
+    }
 }
 
 impl serde::Serialize for TranscribeError {
@@ -30,8 +73,39 @@ impl serde::Serialize for TranscribeError {
     }
 }
 
+/// How speakers are labelled in the transcript
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpeakerLabelMode {
+    /// Speaker A, B, C...
+    Generic,
+    /// Detect names from the conversation
+    AutoNames,
+    /// Names from `speaker_values`
+    KnownNames,
+    Interview,
+    Podcast,
+    Panel,
+    /// Roles from `speaker_values`
+    CustomRoles,
+    /// Roles from the saved role set `role_set_id`
+    RoleSet,
+}
+
+impl SpeakerLabelMode {
+    /// Roles for the built-in conversation types (empty for every other mode)
+    pub fn preset_roles(self) -> &'static [&'static str] {
+        match self {
+            Self::Interview => &["Interviewer", "Interviewee"],
+            Self::Podcast => &["Host", "Guest"],
+            Self::Panel => &["Moderator", "Panelist"],
+            _ => &[],
+        }
+    }
+}
+
 /// Options for transcription request
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct TranscriptionOptions {
     pub max_speakers: Option<i32>, // None = auto (defaults to 1-10), Some(n) = max n speakers
     pub boost_words: Vec<String>,
@@ -39,123 +113,104 @@ pub struct TranscriptionOptions {
     pub detect_topics: bool,
     pub analyze_sentiment: bool,
     pub extract_key_phrases: bool,  // auto_highlights in AssemblyAI
-    pub speaker_label_mode: String,  // 'generic' | 'auto-names' | 'known-names' | 'interview' | etc.
+    pub speaker_label_mode: SpeakerLabelMode,
     pub speaker_values: Vec<String>,  // User-provided names or custom roles
+    #[serde(default)]
+    pub role_set_id: Option<String>,  // Saved role set to use with SpeakerLabelMode::RoleSet
+    #[serde(default)]
+    pub language_code: Option<String>,  // e.g. "en_us", "es"; None = provider default
+    #[serde(default)]
+    pub language_detection: bool,  // Detect the dominant language instead of using language_code
+    #[serde(default)]
+    pub redact_pii: bool,
+    #[serde(default)]
+    pub redact_pii_policies: Vec<String>,  // e.g. "person_name", "phone_number", "medical_condition"
+    #[serde(default)]
+    pub auto_chapters: bool,
+    #[serde(default)]
+    pub entity_detection: bool,
+    #[serde(default)]
+    pub filter_profanity: bool,
+    #[serde(default)]
+    pub disfluencies: bool,  // Keep "um", "uh" etc. in the transcript
+    #[serde(default)]
+    pub boost_param: Option<String>,  // 'low' | 'default' | 'high' weight for boost_words
+    #[serde(default)]
+    pub custom_spelling: Vec<CustomSpelling>,  // Built from the selected vocabularies' spellings
+    #[serde(default)]
+    pub vocabulary_ids: Vec<String>,  // Selected vocabularies; their terms are ranked into boost_words
 }
 
-/// Response from upload endpoint
-#[derive(Debug, Deserialize)]
-struct UploadResponse {
-    upload_url: String,
+/// Rewrite any of `from` in the transcript as `to` (e.g. "sema glue tide" -> "semaglutide")
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct CustomSpelling {
+    pub from: Vec<String>,
+    pub to: String,
 }
 
-/// Speaker identification configuration
-#[derive(Debug, Serialize, Clone)]
-struct SpeakerIdentification {
-    speaker_type: String,  // "role" or "name"
-    known_values: Vec<String>,
-}
+// Retry budgets per command: uploads are large and slow to repeat, submissions
+// should not pile up duplicates (a timed-out submit may already be billed, so it
+// isn't retried), polls are cheap and idempotent
+pub(crate) const UPLOAD_RETRY: RetryPolicy = RetryPolicy::new(3, Duration::from_secs(2), Duration::from_secs(30));
+pub(crate) const SUBMIT_RETRY: RetryPolicy =
+    RetryPolicy::new(2, Duration::from_secs(1), Duration::from_secs(10)).non_idempotent();
+pub(crate) const POLL_RETRY: RetryPolicy = RetryPolicy::new(5, Duration::from_secs(1), Duration::from_secs(60));
 
-/// Speech understanding request wrapper
-#[derive(Debug, Serialize, Clone)]
-struct SpeechUnderstandingRequest {
-    speaker_identification: SpeakerIdentification,
-}
+/// Event name used for byte-level upload progress
+pub const UPLOAD_PROGRESS_EVENT: &str = "upload-progress";
 
-/// Speech understanding wrapper
+/// Upload progress, emitted as `upload-progress`
 #[derive(Debug, Serialize, Clone)]
-struct SpeechUnderstanding {
-    request: SpeechUnderstandingRequest,
-}
-
-/// Request body for transcript submission
-#[derive(Debug, Serialize)]
-struct TranscriptRequest {
-    audio_url: String,
-    speaker_labels: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    speakers_expected: Option<i32>,  // Used when exact count known
-    #[serde(skip_serializing_if = "Vec::is_empty")]
-    word_boost: Vec<String>,
-    #[serde(skip_serializing_if = "std::ops::Not::not")]
-    summarization: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    summary_model: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    summary_type: Option<String>,
-    #[serde(skip_serializing_if = "std::ops::Not::not")]
-    iab_categories: bool,
-    #[serde(skip_serializing_if = "std::ops::Not::not")]
-    sentiment_analysis: bool,
-    #[serde(skip_serializing_if = "std::ops::Not::not")]
-    auto_highlights: bool,  // Key phrases extraction
-    #[serde(skip_serializing_if = "Option::is_none")]
-    speech_understanding: Option<SpeechUnderstanding>,
+pub struct UploadProgress {
+    pub upload_id: String,
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
 }
 
-/// Response from transcript submission and polling
-/// Using flatten to capture any extra fields we don't explicitly handle
-#[derive(Debug, Deserialize, Serialize)]
-pub struct TranscriptResponse {
-    pub id: String,
-    pub status: String,
-    #[serde(default)]
-    pub text: Option<String>,
-    #[serde(default)]
-    pub utterances: Option<Vec<Utterance>>,
-    #[serde(default)]
-    pub summary: Option<String>,
-    #[serde(default)]
-    pub iab_categories_result: Option<IabCategoriesResult>,
-    #[serde(default)]
-    pub sentiment_analysis_results: Option<Vec<SentimentResult>>,
-    #[serde(default)]
-    pub error: Option<String>,
-    // Capture any other fields we don't explicitly handle
-    #[serde(flatten)]
-    pub extra: std::collections::HashMap<String, serde_json::Value>,
-}
+/// Cancellation handles for uploads in flight, keyed by upload ID
+#[derive(Default)]
+pub struct ActiveUploads(Mutex<HashMap<String, CancellationToken>>);
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct Utterance {
-    pub speaker: String,
-    pub text: String,
-    pub start: i64,
-    pub end: i64,
-    #[serde(default)]
-    pub words: Vec<Word>,
+// A cancellation handle in `ActiveUploads` or `ActiveWaits`. Dropping it removes the
+// handle, so an operation abandoned part way (the rest of a failed parallel job) doesn't
+// leave it behind.
+pub(crate) struct Registration<'a> {
+    handles: &'a Mutex<HashMap<String, CancellationToken>>,
+    key: String,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct Word {
-    pub text: String,
-    pub start: i64,
-    pub end: i64,
-    #[serde(default)]
-    pub speaker: Option<String>,
+impl<'a> Registration<'a> {
+    fn new(handles: &'a Mutex<HashMap<String, CancellationToken>>, key: &str, token: &CancellationToken) -> Self {
+        handles.lock().unwrap().insert(key.to_string(), token.clone());
+        Self {
+            handles,
+            key: key.to_string(),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct IabCategoriesResult {
-    #[serde(default)]
-    pub summary: std::collections::HashMap<String, f64>,
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.handles.lock().unwrap().remove(&self.key);
+    }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-pub struct SentimentResult {
-    pub text: String,
-    pub start: i64,
-    pub end: i64,
-    pub sentiment: String, // "POSITIVE", "NEGATIVE", "NEUTRAL"
-    pub confidence: f64,
-    #[serde(default)]
-    pub speaker: Option<String>,
+// AssemblyAI client pointed at the configured base URL
+pub(crate) fn assemblyai_provider(app: &AppHandle, api_key: String, retry: RetryPolicy) -> AssemblyAiProvider {
+    AssemblyAiProvider::new(shared_client(app), load_api_endpoints(app).assemblyai_v2(), api_key)
+        .with_retry_policy(retry)
 }
 
 /// Upload an audio file to AssemblyAI and return the upload URL
-/// AssemblyAI expects the raw audio bytes in the request body, not multipart form
+/// The file is streamed from disk in chunks, emitting `upload-progress` events as it goes.
+/// Passing an `upload_id` lets the frontend cancel the upload with `cancel_upload`.
 #[tauri::command]
-pub async fn upload_audio(file_path: String, api_key: String) -> Result<String, TranscribeError> {
+pub async fn upload_audio(
+    app: AppHandle,
+    file_path: String,
+    api_key: String,
+    upload_id: Option<String>,
+) -> Result<String, TranscribeError> {
     info!("Starting upload for file: {}", file_path);
     
     let path = PathBuf::from(&file_path);
@@ -166,249 +221,779 @@ pub async fn upload_audio(file_path: String, api_key: String) -> Result<String,
         return Err(TranscribeError::FileError(format!("File does not exist: {}", file_path)));
     }
     
-    // Read the file
-    info!("Opening file...");
-    let mut file = File::open(&path)
-        .await
-        .map_err(|e| {
-            error!("Failed to open file: {}", e);
-            TranscribeError::FileError(format!("Failed to open file: {}", e))
-        })?;
+    let provider = assemblyai_provider(&app, api_key, UPLOAD_RETRY);
+    let upload_id = upload_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    upload_cancellable(&app, &provider, &path, &upload_id).await
+}
+
+// Run a provider upload that reports progress and can be stopped with `cancel_upload`
+pub(crate) async fn upload_cancellable(
+    app: &AppHandle,
+    provider: &dyn TranscriptionProvider,
+    path: &Path,
+    upload_id: &str,
+) -> Result<String, TranscribeError> {
+    let cancel_token = CancellationToken::new();
+    let _registration = app
+        .try_state::<ActiveUploads>()
+        .map(|uploads| Registration::new(&uploads.inner().0, upload_id, &cancel_token));
     
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer)
-        .await
-        .map_err(|e| {
-            error!("Failed to read file: {}", e);
-            TranscribeError::FileError(format!("Failed to read file: {}", e))
-        })?;
-    
-    info!("File read successfully, size: {} bytes", buffer.len());
-    
-    // Log API key presence (not the actual key!)
-    info!("API key present: {}, length: {}", !api_key.is_empty(), api_key.len());
-    
-    // AssemblyAI upload endpoint expects raw bytes in body with Content-Type header
-    info!("Uploading to AssemblyAI...");
-    let client = reqwest::Client::new();
-    let response = client
-        .post(format!("{}/upload", ASSEMBLYAI_API_BASE))
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/octet-stream")
-        .body(buffer)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Upload request failed: {}", e);
-            TranscribeError::RequestFailed(format!("Upload request failed: {}", e))
-        })?;
+    let progress_app = app.clone();
+    let progress_id = upload_id.to_string();
+    let on_progress = Arc::new(move |bytes_sent: u64, total_bytes: u64| {
+        let progress = UploadProgress {
+            upload_id: progress_id.clone(),
+            bytes_sent,
+            total_bytes,
+        };
+        if let Err(e) = progress_app.emit(UPLOAD_PROGRESS_EVENT, progress) {
+            warn!("Failed to emit upload progress: {}", e);
+        }
+    });
     
-    let status = response.status();
-    info!("Upload response status: {}", status);
+    let result = tokio::select! {
+        _ = cancel_token.cancelled() => Err(TranscribeError::Cancelled),
+        result = provider.upload(path, on_progress) => result,
+    };
     
-    if !status.is_success() {
-        let text = response.text().await.unwrap_or_default();
-        error!("Upload failed with status {}: {}", status, text);
-        return Err(TranscribeError::ApiError(format!(
-            "Upload failed with status {}: {}",
-            status, text
-        )));
+    if matches!(result, Err(TranscribeError::Cancelled)) {
+        info!("Upload {} cancelled", upload_id);
+    }
+    result
+}
+
+/// Cancel an upload started with the given `upload_id`
+/// Returns false if no such upload is in flight
+#[tauri::command]
+pub async fn cancel_upload(
+    uploads: tauri::State<'_, ActiveUploads>,
+    upload_id: String,
+) -> Result<bool, TranscribeError> {
+    match uploads.0.lock().unwrap().remove(&upload_id) {
+        Some(token) => {
+            info!("Cancelling upload: {}", upload_id);
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
     }
-    
-    let upload_response: UploadResponse = response
-        .json()
-        .await
-        .map_err(|e| {
-            error!("Failed to parse upload response: {}", e);
-            TranscribeError::RequestFailed(format!("Failed to parse upload response: {}", e))
-        })?;
-    
-    info!("Upload successful! URL: {}", upload_response.upload_url);
-    Ok(upload_response.upload_url)
 }
 
 /// Submit a transcription job to AssemblyAI
 #[tauri::command]
 pub async fn submit_transcription(
+    app: AppHandle,
     upload_url: String,
     api_key: String,
     options: TranscriptionOptions,
 ) -> Result<String, TranscribeError> {
     info!("Submitting transcription for: {}", upload_url);
     
-    // Build speech_understanding config based on speaker label mode
-    let speech_understanding = match options.speaker_label_mode.as_str() {
-        "auto-names" => Some(SpeechUnderstanding {
-            request: SpeechUnderstandingRequest {
-                speaker_identification: SpeakerIdentification {
-                    speaker_type: "name".to_string(),
-                    known_values: vec![],  // Empty = auto-detect names from conversation
-                },
-            },
-        }),
-        "known-names" => Some(SpeechUnderstanding {
-            request: SpeechUnderstandingRequest {
-                speaker_identification: SpeakerIdentification {
-                    speaker_type: "name".to_string(),
-                    known_values: options.speaker_values.clone(),
-                },
-            },
-        }),
-        "interview" => Some(SpeechUnderstanding {
-            request: SpeechUnderstandingRequest {
-                speaker_identification: SpeakerIdentification {
-                    speaker_type: "role".to_string(),
-                    known_values: vec!["Interviewer".to_string(), "Interviewee".to_string()],
-                },
-            },
-        }),
-        "podcast" => Some(SpeechUnderstanding {
-            request: SpeechUnderstandingRequest {
-                speaker_identification: SpeakerIdentification {
-                    speaker_type: "role".to_string(),
-                    known_values: vec!["Host".to_string(), "Guest".to_string()],
-                },
-            },
-        }),
-        "panel" => Some(SpeechUnderstanding {
-            request: SpeechUnderstandingRequest {
-                speaker_identification: SpeakerIdentification {
-                    speaker_type: "role".to_string(),
-                    known_values: vec!["Moderator".to_string(), "Panelist".to_string()],
-                },
-            },
-        }),
-        "custom-roles" => Some(SpeechUnderstanding {
-            request: SpeechUnderstandingRequest {
-                speaker_identification: SpeakerIdentification {
-                    speaker_type: "role".to_string(),
-                    known_values: options.speaker_values.clone(),
-                },
-            },
-        }),
-        _ => None,  // "generic" or unrecognized - no speaker identification
-    };
-    
-    if speech_understanding.is_some() {
-        info!("Speaker identification enabled with mode: {:?}", options.speaker_label_mode);
-    }
-    
-    let mut request = TranscriptRequest {
-        audio_url: upload_url,
-        speaker_labels: true,
-        speakers_expected: options.max_speakers,  // Use as hint for max speakers
-        word_boost: options.boost_words,
-        summarization: options.include_summary,
-        summary_model: if options.include_summary { Some("informative".to_string()) } else { None },
-        summary_type: if options.include_summary { Some("bullets".to_string()) } else { None },
-        iab_categories: options.detect_topics,
-        sentiment_analysis: options.analyze_sentiment,
-        auto_highlights: options.extract_key_phrases,
-        speech_understanding,
-    };
+    submit_boosted(&app, &assemblyai_provider(&app, api_key, SUBMIT_RETRY), &upload_url, &options).await
+}
+
+// Submit with the word boost list ranked down to what the API accepts,
+// remembering the boosted terms so their hits can be counted later
+pub(crate) async fn submit_boosted(
+    app: &AppHandle,
+    provider: &dyn TranscriptionProvider,
+    upload_url: &str,
+    options: &TranscriptionOptions,
+) -> Result<String, TranscribeError> {
+    let options = apply_word_boost(app, &apply_role_set(app, options)?)
+        .await
+        .map_err(|e| TranscribeError::FileError(e.to_string()))?;
     
-    // Ensure word_boost doesn't exceed limits
-    if request.word_boost.len() > 200 {
-        request.word_boost.truncate(200);
+    let transcript_id = provider.submit(upload_url, &options).await?;
+    track_boosted_terms(app, &transcript_id, &options.boost_words);
+    Ok(transcript_id)
+}
+
+// Settle word boost stats once a transcript has finished either way
+fn record_outcome(app: &AppHandle, transcript_id: &str, result: &Result<TranscriptResponse, TranscribeError>) {
+    match result {
+        Ok(response) if response.status == TranscriptStatus::Completed => {
+            record_boost_hits(app, transcript_id, response.text.as_deref());
+        }
+        Err(TranscribeError::TranscriptionFailed(_)) => record_boost_hits(app, transcript_id, None),
+        _ => {}
     }
+}
+
+/// Poll for transcription completion
+#[tauri::command]
+pub async fn poll_transcription(
+    app: AppHandle,
+    transcript_id: String,
+    api_key: String,
+) -> Result<TranscriptResponse, TranscribeError> {
+    info!("Polling transcription: {}", transcript_id);
     
-    info!("Request: {:?}", request);
+    let result = poll_checked(&assemblyai_provider(&app, api_key, POLL_RETRY), &transcript_id).await;
+    record_outcome(&app, &transcript_id, &result);
+    result
+}
+
+// Poll once, turning an errored transcript into a TranscriptionFailed error
+async fn poll_checked(
+    provider: &dyn TranscriptionProvider,
+    transcript_id: &str,
+) -> Result<TranscriptResponse, TranscribeError> {
+    let transcript_response = provider.poll(transcript_id).await?;
     
-    let client = reqwest::Client::new();
-    let response = client
-        .post(format!("{}/transcript", ASSEMBLYAI_API_BASE))
-        .header("Authorization", &api_key)
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| {
-            error!("Transcription request failed: {}", e);
-            TranscribeError::RequestFailed(format!("Transcription request failed: {}", e))
-        })?;
+    info!("Poll result - Status: {:?}", transcript_response.status);
     
-    let status = response.status();
-    info!("Transcription submit response status: {}", status);
+    // Check for error status
+    if transcript_response.status == TranscriptStatus::Error {
+        let err_msg = transcript_response.error.clone().unwrap_or_else(|| "Unknown error".to_string());
+        error!("Transcription failed: {}", err_msg);
+        return Err(TranscribeError::TranscriptionFailed(err_msg));
+    }
     
-    if !status.is_success() {
-        let text = response.text().await.unwrap_or_default();
-        error!("Transcription submission failed with status {}: {}", status, text);
-        return Err(TranscribeError::ApiError(format!(
-            "Transcription submission failed with status {}: {}",
-            status, text
-        )));
+    Ok(transcript_response)
+}
+
+/// Event name used for transcript status changes while waiting
+pub const TRANSCRIPT_STATUS_EVENT: &str = "transcript-status";
+
+// Default deadline for a wait when the caller doesn't give one
+pub(crate) const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Status change seen while waiting on a transcript, emitted as `transcript-status`
+#[derive(Debug, Serialize, Clone)]
+pub struct TranscriptStatusEvent {
+    pub job_id: String,
+    pub transcript_id: String,
+    pub status: TranscriptStatus,
+}
+
+/// Cancellation handles for transcript waits in flight, keyed by job ID
+#[derive(Default)]
+pub struct ActiveWaits(Mutex<HashMap<String, CancellationToken>>);
+
+// Register a wait under `job_id`, so `cancel_transcription_wait` can stop it
+pub(crate) fn register_wait<'a>(
+    app: &'a AppHandle,
+    job_id: &str,
+    cancel_token: &CancellationToken,
+) -> Option<Registration<'a>> {
+    app.try_state::<ActiveWaits>()
+        .map(|waits| Registration::new(&waits.inner().0, job_id, cancel_token))
+}
+
+/// How often to poll while waiting on a transcript.
+/// Polls start at `min_interval` and slow down by half again each time the status
+/// is unchanged, up to `max_interval`; any status change resets to `min_interval`.
+#[derive(Debug, Clone, Copy)]
+pub struct PollSchedule {
+    pub min_interval: Duration,
+    pub max_interval: Duration,
+}
+
+impl Default for PollSchedule {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(2),
+            max_interval: Duration::from_secs(15),
+        }
     }
+}
+
+impl PollSchedule {
+    fn next_interval(&self, current: Duration, status_changed: bool) -> Duration {
+        if status_changed {
+            self.min_interval
+        } else {
+            current.mul_f32(1.5).min(self.max_interval)
+        }
+    }
+}
+
+/// Poll until the transcript is completed or errored.
+/// Fails with `Timeout` once `timeout` has passed and with `Cancelled` if `cancel` fires;
+/// `on_status` is called whenever the status changes (including the first poll).
+pub async fn wait_for_transcript(
+    provider: &dyn TranscriptionProvider,
+    transcript_id: &str,
+    schedule: &PollSchedule,
+    timeout: Duration,
+    cancel: &CancellationToken,
+    mut on_status: impl FnMut(TranscriptStatus) + Send,
+) -> Result<TranscriptResponse, TranscribeError> {
+    let polling = async {
+        let mut last_status = None;
+        let mut interval = schedule.min_interval;
     
-    // Get raw response text first for debugging
-    let response_text = response.text().await.map_err(|e| {
-        error!("Failed to get response text: {}", e);
-        TranscribeError::RequestFailed(format!("Failed to get response text: {}", e))
-    })?;
+        loop {
+            let response = provider.poll(transcript_id).await?;
+            let status_changed = last_status != Some(response.status);
+            if status_changed {
+                info!("Transcript {} is now {:?}", transcript_id, response.status);
+                on_status(response.status);
+                last_status = Some(response.status);
+            }
     
-    info!("Raw response (first 500 chars): {}", &response_text.chars().take(500).collect::<String>());
+            match response.status {
+                TranscriptStatus::Completed => return Ok(response),
+                TranscriptStatus::Error => {
+                    let err_msg = response.error.unwrap_or_else(|| "Unknown error".to_string());
+                    error!("Transcription failed: {}", err_msg);
+                    return Err(TranscribeError::TranscriptionFailed(err_msg));
+                }
+                TranscriptStatus::Queued | TranscriptStatus::Processing => {}
+            }
     
-    // Parse the response
-    let transcript_response: TranscriptResponse = serde_json::from_str(&response_text)
-        .map_err(|e| {
-            error!("Failed to parse transcript response: {}. Response was: {}", e, &response_text.chars().take(1000).collect::<String>());
-            TranscribeError::RequestFailed(format!("Failed to parse transcript response: {}", e))
-        })?;
+            interval = schedule.next_interval(interval, status_changed);
+            tokio::time::sleep(interval).await;
+        }
+    };
     
-    info!("Transcription submitted! ID: {}", transcript_response.id);
-    Ok(transcript_response.id)
+    tokio::select! {
+        _ = cancel.cancelled() => {
+            info!("Stopped waiting on transcript {}", transcript_id);
+            Err(TranscribeError::Cancelled)
+        }
+        result = tokio::time::timeout(timeout, polling) => result.unwrap_or(Err(TranscribeError::Timeout)),
+    }
 }
 
-/// Poll for transcription completion
+// Wait on a transcript under `job_id`, so `cancel_transcription_wait` can stop it,
+// emitting `transcript-status` events as the status changes
+pub(crate) async fn wait_tracked(
+    app: &AppHandle,
+    provider: &dyn TranscriptionProvider,
+    job_id: &str,
+    transcript_id: &str,
+    timeout: Duration,
+    mut on_status: impl FnMut(TranscriptStatus) + Send,
+) -> Result<TranscriptResponse, TranscribeError> {
+    let cancel_token = CancellationToken::new();
+    let _registration = register_wait(app, job_id, &cancel_token);
+    
+    let result = wait_for_transcript(provider, transcript_id, &PollSchedule::default(), timeout, &cancel_token, |status| {
+        let event = TranscriptStatusEvent {
+            job_id: job_id.to_string(),
+            transcript_id: transcript_id.to_string(),
+            status,
+        };
+        if let Err(e) = app.emit(TRANSCRIPT_STATUS_EVENT, event) {
+            warn!("Failed to emit transcript status: {}", e);
+        }
+        on_status(status);
+    })
+    .await;
+    
+    record_outcome(app, transcript_id, &result);
+    result
+}
+
+/// Wait for a transcript to finish, polling on the backend
+/// Status changes are emitted as `transcript-status` events tagged with `job_id`,
+/// which can also be passed to `cancel_transcription_wait`. Gives up after
+/// `timeout_secs` (30 minutes if not given).
 #[tauri::command]
-pub async fn poll_transcription(
+pub async fn wait_for_transcription(
+    app: AppHandle,
     transcript_id: String,
     api_key: String,
+    job_id: String,
+    timeout_secs: Option<u64>,
 ) -> Result<TranscriptResponse, TranscribeError> {
-    info!("Polling transcription: {}", transcript_id);
+    let timeout = timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_WAIT_TIMEOUT);
+    info!("Waiting for transcription {} (job {}, timeout {:?})", transcript_id, job_id, timeout);
     
-    let client = reqwest::Client::new();
-    let response = client
-        .get(format!("{}/transcript/{}", ASSEMBLYAI_API_BASE, transcript_id))
-        .header("Authorization", &api_key)
-        .send()
+    let provider = assemblyai_provider(&app, api_key, POLL_RETRY);
+    wait_tracked(&app, &provider, &job_id, &transcript_id, timeout, |_| {}).await
+}
+
+/// Stop waiting on the transcript for `job_id`. For chunk and track jobs this stops
+/// every file's upload, submission and wait (see chunked.rs).
+/// Returns false if nothing is waiting under that ID
+#[tauri::command]
+pub async fn cancel_transcription_wait(
+    waits: tauri::State<'_, ActiveWaits>,
+    job_id: String,
+) -> Result<bool, TranscribeError> {
+    match waits.0.lock().unwrap().remove(&job_id) {
+        Some(token) => {
+            info!("Cancelling wait for job: {}", job_id);
+            token.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Event name used for transcription job progress updates
+pub const TRANSCRIPTION_JOB_EVENT: &str = "transcription-job";
+
+// How long a server-side job waits on AssemblyAI before giving up
+const JOB_POLL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Pipeline stage of a backend transcription job
+/// Names match the frontend `FileJob` statuses
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStage {
+    Queued,
+    Converting,
+    Uploading,
+    Transcribing,
+    Saving,
+    Complete,
+    Error,
+}
+
+/// Progress update for a transcription job, emitted as `transcription-job`
+#[derive(Debug, Serialize, Clone)]
+pub struct TranscriptionJobEvent {
+    pub job_id: String,
+    pub stage: JobStage,
+    pub progress: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Latest known state of every job started this session
+/// Lets a window that subscribes late catch up on a job's progress
+#[derive(Default)]
+pub struct TranscriptionJobs(Mutex<HashMap<String, TranscriptionJobEvent>>);
+
+// Record the job's new state and broadcast it to the frontend
+fn emit_job_event(app: &AppHandle, event: TranscriptionJobEvent) {
+    if let Some(jobs) = app.try_state::<TranscriptionJobs>() {
+        jobs.0
+            .lock()
+            .unwrap()
+            .insert(event.job_id.clone(), event.clone());
+    }
+    if let Err(e) = app.emit(TRANSCRIPTION_JOB_EVENT, event) {
+        warn!("Failed to emit transcription job event: {}", e);
+    }
+}
+
+fn emit_job_stage(app: &AppHandle, job_id: &str, stage: JobStage, progress: u8) {
+    emit_job_event(app, TranscriptionJobEvent {
+        job_id: job_id.to_string(),
+        stage,
+        progress,
+        history_id: None,
+        error: None,
+    });
+}
+
+/// Start a transcription job that runs the whole pipeline in the backend:
+/// convert → upload → submit → poll → store audio → save history entry.
+/// Returns the job ID immediately; progress is reported through `transcription-job` events.
+/// The job is recorded under the app data directory so it can resume after a restart.
+/// `convert` picks the tracks, parts and audio cleanup, as for `convert_to_audio`.
+#[tauri::command]
+pub async fn start_transcription_job(
+    app: AppHandle,
+    input_path: String,
+    api_key: String,
+    options: TranscriptionOptions,
+    convert: Option<ConvertOptions>,
+) -> Result<String, TranscribeError> {
+    if !PathBuf::from(&input_path).exists() {
+        return Err(TranscribeError::FileError(format!("File does not exist: {}", input_path)));
+    }
+    
+    let job_id = Uuid::new_v4().to_string();
+    info!("Starting transcription job {} for: {}", job_id, input_path);
+    
+    let mut record = JobRecord::new(job_id.clone(), input_path, options, convert.unwrap_or_default());
+    save_job(&app, &mut record).map_err(|e| TranscribeError::SaveFailed(e.to_string()))?;
+    emit_job_stage(&app, &job_id, JobStage::Queued, 0);
+    
+    spawn_transcription_job(app, record, api_key);
+    
+    Ok(job_id)
+}
+
+/// Get the latest state of a transcription job started this session
+#[tauri::command]
+pub async fn get_transcription_job(
+    jobs: tauri::State<'_, TranscriptionJobs>,
+    job_id: String,
+) -> Result<Option<TranscriptionJobEvent>, TranscribeError> {
+    Ok(jobs.0.lock().unwrap().get(&job_id).cloned())
+}
+
+/// Pick up jobs left unfinished by a previous run.
+/// Jobs that already have a transcript ID go straight back to polling the provider.
+pub fn resume_transcription_jobs(app: &AppHandle) {
+    let records = match load_jobs(app) {
+        Ok(records) => records,
+        Err(e) => {
+            error!("Failed to load persisted jobs: {}", e);
+            return;
+        }
+    };
+    
+    let unfinished: Vec<JobRecord> = records.into_iter().filter(|r| r.is_unfinished()).collect();
+    if unfinished.is_empty() {
+        return;
+    }
+    info!("Resuming {} unfinished transcription job(s)", unfinished.len());
+    
+    let api_key = load_api_key(app).ok().flatten();
+    
+    for mut record in unfinished {
+        match &api_key {
+            Some(key) => spawn_transcription_job(app.clone(), record, key.clone()),
+            None => {
+                let err = TranscribeError::ApiError("No API key configured to resume the job".to_string());
+                fail_job(app, &mut record, &err);
+            }
+        }
+    }
+}
+
+// Run the job in the background, reporting the final outcome
+fn spawn_transcription_job(app: AppHandle, mut record: JobRecord, api_key: String) {
+    tauri::async_runtime::spawn(async move {
+        match run_transcription_job(&app, &mut record, api_key).await {
+            Ok(history_id) => {
+                info!("Transcription job {} complete, history entry: {}", record.job_id, history_id);
+                if let Err(e) = remove_job(&app, &record.job_id) {
+                    warn!("Failed to remove finished job record: {}", e);
+                }
+                emit_job_event(&app, TranscriptionJobEvent {
+                    job_id: record.job_id,
+                    stage: JobStage::Complete,
+                    progress: 100,
+                    history_id: Some(history_id),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                error!("Transcription job {} failed: {}", record.job_id, e);
+                fail_job(&app, &mut record, &e);
+            }
+        }
+    });
+}
+
+// Keep failed jobs on disk so the UI can show them until dismissed
+fn fail_job(app: &AppHandle, record: &mut JobRecord, err: &TranscribeError) {
+    record.stage = JobStage::Error;
+    record.error = Some(err.to_string());
+    if let Err(e) = save_job(app, record) {
+        warn!("Failed to persist job {}: {}", record.job_id, e);
+    }
+    emit_job_event(app, TranscriptionJobEvent {
+        job_id: record.job_id.clone(),
+        stage: JobStage::Error,
+        progress: 0,
+        history_id: None,
+        error: record.error.clone(),
+    });
+}
+
+// Move the job to a new stage, on disk and in the UI
+fn advance_job(app: &AppHandle, record: &mut JobRecord, stage: JobStage, progress: u8) {
+    record.stage = stage;
+    if let Err(e) = save_job(app, record) {
+        warn!("Failed to persist job {}: {}", record.job_id, e);
+    }
+    emit_job_stage(app, &record.job_id, stage, progress);
+}
+
+// Run every remaining pipeline stage, cleaning up the converted audio whatever the outcome
+async fn run_transcription_job(
+    app: &AppHandle,
+    record: &mut JobRecord,
+    api_key: String,
+) -> Result<String, TranscribeError> {
+    let input_exists = PathBuf::from(&record.input_path).exists();
+    
+    // A job that was already submitted goes straight back to polling; its source is then
+    // only converted for playback, once the transcript is ready
+    let mut conversion = None;
+    if record.transcript_id.is_none() {
+        if input_exists {
+            advance_job(app, record, JobStage::Converting, 10);
+            conversion = Some(convert_job_audio(app, record).await?);
+        } else if record.upload_url.is_some() {
+            warn!("Source file for job {} is gone; continuing without playback audio", record.job_id);
+        } else {
+            return Err(TranscribeError::FileError(format!("File does not exist: {}", record.input_path)));
+        }
+    }
+    
+    // Jobs run unattended, so they get the most patient budget
+    let provider = assemblyai_provider(app, api_key.clone(), POLL_RETRY);
+    info!("Job {} transcribing with {}", record.job_id, provider.name());
+    let result = transcribe_and_save(app, &provider, api_key, record, &mut conversion).await;
+    
+    if let Some(conversion) = conversion {
+        if let Err(e) = cleanup_temp_dir(app.clone(), conversion.temp_dir).await {
+            warn!("Failed to cleanup temp dir for job {}: {}", record.job_id, e);
+        }
+    }
+    
+    result
+}
+
+// Convert the job's source with its options, keeping the timeline for remapping
+async fn convert_job_audio(app: &AppHandle, record: &mut JobRecord) -> Result<ConversionResult, TranscribeError> {
+    let conversion = convert_to_audio(
+        app.clone(),
+        record.input_path.clone(),
+        None,
+        Some(record.job_id.clone()),
+        Some(record.convert.clone()),
+    )
+    .await
+    .map_err(|e| TranscribeError::ConversionFailed(e.to_string()))?;
+    record.timeline = conversion.timeline.clone();
+    Ok(conversion)
+}
+
+async fn transcribe_and_save(
+    app: &AppHandle,
+    provider: &dyn TranscriptionProvider,
+    api_key: String,
+    record: &mut JobRecord,
+    conversion: &mut Option<ConversionResult>,
+) -> Result<String, TranscribeError> {
+    let history_options = HistoryOptions::from(&record.options);
+    
+    if record.transcript_id.is_none() {
+        let audio_path = conversion.as_ref().map(|c| c.output_path.clone());
+        let upload_url = match (&record.upload_url, &audio_path) {
+            (Some(url), _) => url.clone(),
+            (None, Some(path)) => {
+                advance_job(app, record, JobStage::Uploading, 30);
+                let url = upload_cancellable(app, provider, Path::new(path), &record.job_id).await?;
+                record.upload_url = Some(url.clone());
+                // Persisted so a restart before submitting doesn't upload again
+                if let Err(e) = save_job(app, record) {
+                    warn!("Failed to persist job {}: {}", record.job_id, e);
+                }
+                url
+            }
+            (None, None) => {
+                return Err(TranscribeError::FileError("No audio available to upload".to_string()));
+            }
+        };
+    
+        let transcript_id = submit_boosted(app, provider, &upload_url, &record.options).await?;
+        record.transcript_id = Some(transcript_id);
+    }
+    
+    advance_job(app, record, JobStage::Transcribing, 50);
+    let transcript_id = record.transcript_id.clone().unwrap_or_default();
+    let mut response = wait_for_completion(app, provider, &record.job_id, &transcript_id).await?;
+    if let Some(timeline) = &record.timeline {
+        timeline.remap_transcript(&mut response);
+    }
+    
+    advance_job(app, record, JobStage::Saving, 85);
+    let history_id = new_history_id();
+    
+    // A resumed job skipped conversion; playback audio is optional, so failures only warn
+    if conversion.is_none() {
+        if PathBuf::from(&record.input_path).exists() {
+            match convert_job_audio(app, record).await {
+                Ok(converted) => *conversion = Some(converted),
+                Err(e) => warn!("Failed to convert audio for playback: {}", e),
+            }
+        } else {
+            warn!("Source file for job {} is gone; saving without playback audio", record.job_id);
+        }
+    }
+    let audio_path = conversion.as_ref().map(|c| c.output_path.clone());
+    
+    // Audio playback is optional, so a failed copy doesn't fail the job
+    let stored_audio = match audio_path {
+        Some(path) => match store_audio_file(path, history_id.clone(), app.clone()).await {
+            Ok(stored) => Some(stored),
+            Err(e) => {
+                warn!("Failed to store audio for playback: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    
+    let mut entry = build_history_entry(&history_id, &record.input_path, &response, &history_options, stored_audio);
+    // Players use this to find source times in the stored (trimmed) audio
+    if let Some(timeline) = &record.timeline {
+        entry["timeline"] = serde_json::json!(timeline);
+    }
+    entry["cleanupProfile"] = serde_json::json!(record.convert.cleanup);
+    save_history_entry(app.clone(), entry.to_string())
         .await
-        .map_err(|e| {
-            error!("Poll request failed: {}", e);
-            TranscribeError::RequestFailed(format!("Poll request failed: {}", e))
-        })?;
+        .map_err(|e| TranscribeError::SaveFailed(e.to_string()))?;
+    
+    // The local copy is safe, so the transcript no longer needs to live on AssemblyAI.
+    // A failed delete is logged rather than failing an otherwise finished job.
+    if load_auto_delete_remote(app) {
+        let delete_provider = assemblyai_provider(app, api_key, DELETE_RETRY);
+        if let Err(e) = delete_and_record(app, &delete_provider, &transcript_id, Some(history_id.clone())).await {
+            error!("Auto-delete of remote transcript {} failed: {}", transcript_id, e);
+        }
+    }
+    
+    Ok(history_id)
+}
+
+// Poll until the transcript completes, reporting progress while AssemblyAI processes it
+async fn wait_for_completion(
+    app: &AppHandle,
+    provider: &dyn TranscriptionProvider,
+    job_id: &str,
+    transcript_id: &str,
+) -> Result<TranscriptResponse, TranscribeError> {
+    wait_tracked(app, provider, job_id, transcript_id, JOB_POLL_TIMEOUT, |status| {
+        if status == TranscriptStatus::Processing {
+            emit_job_stage(app, job_id, JobStage::Transcribing, 65);
+        }
+    })
+    .await
+}
+
+/// Transcription settings recorded alongside a history entry
+struct HistoryOptions {
+    speaker_names: Vec<String>,
+    included_summary: bool,
+    included_topics: bool,
+    included_sentiment: bool,
+}
+
+impl From<&TranscriptionOptions> for HistoryOptions {
+    fn from(options: &TranscriptionOptions) -> Self {
+        Self {
+            speaker_names: options.speaker_values.clone(),
+            included_summary: options.include_summary,
+            included_topics: options.detect_topics,
+            included_sentiment: options.analyze_sentiment,
+        }
+    }
+}
+
+// Same shape as the frontend's generateHistoryId: "<millis>-<random>"
+fn new_history_id() -> String {
+    let random = Uuid::new_v4().simple().to_string();
+    format!("{}-{}", Utc::now().timestamp_millis(), &random[..7])
+}
+
+/// Build a history entry in the format the frontend's `HistoryEntry` expects
+/// Mirrors `parseTranscriptResponse` and `createHistoryEntry` in transcription.ts / history.ts
+fn build_history_entry(
+    history_id: &str,
+    input_path: &str,
+    response: &TranscriptResponse,
+    options: &HistoryOptions,
+    audio_path: Option<String>,
+) -> serde_json::Value {
+    // Map speaker labels to user-provided names in order of first appearance
+    let mut speaker_map: HashMap<&str, String> = HashMap::new();
+    let mut segments = Vec::new();
+    
+    for utterance in response.utterances.iter().flatten() {
+        if !speaker_map.contains_key(utterance.speaker.as_str()) {
+            let name = options
+                .speaker_names
+                .get(speaker_map.len())
+                .filter(|n| !n.is_empty())
+                .cloned()
+                .unwrap_or_else(|| utterance.speaker.clone());
+            speaker_map.insert(&utterance.speaker, name);
+        }
     
-    let status = response.status();
+        let sentiment = response
+            .sentiment_analysis_results
+            .iter()
+            .flatten()
+            .find(|s| s.start >= utterance.start && s.end <= utterance.end)
+            .map(|s| s.sentiment.to_lowercase());
     
-    if !status.is_success() {
-        let text = response.text().await.unwrap_or_default();
-        error!("Poll request failed with status {}: {}", status, text);
-        return Err(TranscribeError::ApiError(format!(
-            "Poll request failed with status {}: {}",
-            status, text
-        )));
+        let mut segment = serde_json::json!({
+            "speaker": speaker_map[utterance.speaker.as_str()],
+            "text": utterance.text,
+            "start": utterance.start,
+            "end": utterance.end,
+        });
+        if let Some(sentiment) = sentiment {
+            segment["sentiment"] = serde_json::Value::String(sentiment);
+        }
+        // Keep word timings and confidences so the transcript can be reviewed later
+        if let Some(confidence) = utterance.confidence {
+            segment["confidence"] = serde_json::json!(confidence);
+        }
+        if !utterance.words.is_empty() {
+            segment["words"] = utterance
+                .words
+                .iter()
+                .map(|w| serde_json::json!({
+                    "text": w.text,
+                    "start": w.start,
+                    "end": w.end,
+                    "confidence": w.confidence,
+                }))
+                .collect();
+        }
+        segments.push(segment);
     }
     
-    // Get raw response text first for debugging
-    let response_text = response.text().await.map_err(|e| {
-        error!("Failed to get poll response text: {}", e);
-        TranscribeError::RequestFailed(format!("Failed to get poll response text: {}", e))
-    })?;
+    let mut transcript = serde_json::json!({ "segments": segments });
+    if let Some(summary) = &response.summary {
+        transcript["summary"] = serde_json::Value::String(summary.clone());
+    }
+    if let Some(categories) = &response.iab_categories_result {
+        let mut topics: Vec<(&String, f64)> = categories
+            .summary
+            .iter()
+            .map(|(label, relevance)| (label, relevance * 100.0))
+            .collect();
+        topics.sort_by(|a, b| b.1.total_cmp(&a.1));
+        if !topics.is_empty() {
+            transcript["topics"] = topics
+                .into_iter()
+                .map(|(label, relevance)| serde_json::json!({ "label": label, "relevance": relevance }))
+                .collect();
+        }
+    }
     
-    // Parse the response
-    let transcript_response: TranscriptResponse = serde_json::from_str(&response_text)
-        .map_err(|e| {
-            error!("Failed to parse poll response: {}. Response was: {}", e, &response_text.chars().take(1000).collect::<String>());
-            TranscribeError::RequestFailed(format!("Failed to parse poll response: {}", e))
-        })?;
+    let utterances = response.utterances.as_deref().unwrap_or_default();
+    let word_count: usize = utterances
+        .iter()
+        .map(|u| u.text.split_whitespace().count())
+        .sum();
+    let speaker_count = utterances
+        .iter()
+        .map(|u| u.speaker.as_str())
+        .collect::<HashSet<_>>()
+        .len();
     
-    info!("Poll result - Status: {}", transcript_response.status);
+    let filename = PathBuf::from(input_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
     
-    // Check for error status
-    if transcript_response.status == "error" {
-        let err_msg = transcript_response.error.clone().unwrap_or_else(|| "Unknown error".to_string());
-        error!("Transcription failed: {}", err_msg);
-        return Err(TranscribeError::TranscriptionFailed(err_msg));
+    let mut entry = serde_json::json!({
+        "id": history_id,
+        "filename": filename,
+        "originalPath": input_path,
+        "transcribedAt": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        "speakerCount": speaker_count,
+        "wordCount": word_count,
+        "transcript": transcript,
+        "options": {
+            "speakerNames": options.speaker_names,
+            "includedSummary": options.included_summary,
+            "includedTopics": options.included_topics,
+            "includedSentiment": options.included_sentiment,
+        },
+    });
+    if let Some(audio_path) = audio_path {
+        entry["audioPath"] = serde_json::Value::String(audio_path);
     }
     
-    Ok(transcript_response)
+    entry
 }
@@ -1,5 +1,6 @@
 use log::info;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 use tauri::AppHandle;
@@ -37,6 +38,12 @@ pub struct Vocabulary {
     pub name: String,
     pub category: String,
     pub terms: Vec<String>,
+    /// Term -> misheard variants to rewrite to it, e.g. "semaglutide" <- ["sema glue tide"]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub spellings: BTreeMap<String, Vec<String>>,
+    /// Ranking weight when merged vocabularies exceed the word boost limit (higher wins)
+    #[serde(default)]
+    pub priority: i32,
     #[serde(default)]
     pub is_system: bool,
     #[serde(default)]
@@ -129,6 +136,27 @@ fn load_user_vocabularies(app: &AppHandle) -> Result<UserVocabFile, VocabularyEr
     }
 }
 
+// Trim variants and drop empty ones, self-mappings and terms left without variants
+fn clean_spellings(spellings: BTreeMap<String, Vec<String>>) -> BTreeMap<String, Vec<String>> {
+    spellings
+        .into_iter()
+        .filter_map(|(term, variants)| {
+            let term = term.trim().to_string();
+            let mut cleaned: Vec<String> = Vec::new();
+            for variant in variants {
+                let variant = variant.trim().to_string();
+                if !variant.is_empty()
+                    && !variant.eq_ignore_ascii_case(&term)
+                    && !cleaned.iter().any(|v| v.eq_ignore_ascii_case(&variant))
+                {
+                    cleaned.push(variant);
+                }
+            }
+            (!term.is_empty() && !cleaned.is_empty()).then_some((term, cleaned))
+        })
+        .collect()
+}
+
 fn save_user_vocabularies(app: &AppHandle, data: &UserVocabFile) -> Result<(), VocabularyError> {
     let user_dir = get_user_vocab_dir(app)?;
     let user_file = user_dir.join("vocabularies.json");
@@ -196,6 +224,8 @@ pub async fn create_vocabulary(
     name: String,
     category: String,
     terms: Vec<String>,
+    spellings: Option<BTreeMap<String, Vec<String>>>,
+    priority: Option<i32>,
 ) -> Result<Vocabulary, VocabularyError> {
     info!("Creating vocabulary: {}", name);
     
@@ -205,6 +235,8 @@ pub async fn create_vocabulary(
         name,
         category,
         terms,
+        spellings: clean_spellings(spellings.unwrap_or_default()),
+        priority: priority.unwrap_or_default(),
         is_system: false,
         created_at: now.clone(),
         updated_at: now,
@@ -224,6 +256,8 @@ pub async fn update_vocabulary(
     name: Option<String>,
     category: Option<String>,
     terms: Option<Vec<String>>,
+    spellings: Option<BTreeMap<String, Vec<String>>>,
+    priority: Option<i32>,
 ) -> Result<Vocabulary, VocabularyError> {
     info!("Updating vocabulary: {}", id);
     
@@ -246,6 +280,12 @@ pub async fn update_vocabulary(
     if let Some(t) = terms {
         vocab.terms = t;
     }
+    if let Some(sp) = spellings {
+        vocab.spellings = clean_spellings(sp);
+    }
+    if let Some(p) = priority {
+        vocab.priority = p;
+    }
     vocab.updated_at = Utc::now().to_rfc3339();
     
     let updated = vocab.clone();
@@ -299,6 +339,8 @@ pub async fn duplicate_vocabulary(
         name: new_name,
         category: "my-vocabularies".to_string(), // Always duplicate to user category
         terms: source.terms.clone(),
+        spellings: source.spellings.clone(),
+        priority: source.priority,
         is_system: false,
         created_at: now.clone(),
         updated_at: now,
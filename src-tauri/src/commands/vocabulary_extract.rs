@@ -2,6 +2,16 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use super::settings::load_api_endpoints;
+use crate::http::{send_with_retry, shared_client, RetryPolicy};
+
+/// OpenAI rate limits are common on busy keys; back off and try again. Completions are
+/// billed, so a request that may have reached OpenAI isn't sent again.
+pub const EXTRACTION_RETRY: RetryPolicy =
+    RetryPolicy::new(3, Duration::from_secs(2), Duration::from_secs(30)).non_idempotent();
 
 #[derive(Serialize)]
 struct OpenAIRequest {
@@ -38,13 +48,13 @@ struct ResponseMessage {
     content: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ExtractedVocabulary {
     pub categories: Vec<ExtractedCategory>,
     pub suggested_name: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ExtractedCategory {
     pub name: String,
     pub terms: Vec<String>,
@@ -184,11 +194,23 @@ fn extract_pptx_text(path: &Path) -> Result<String, String> {
 
 #[tauri::command]
 pub async fn extract_vocabulary_terms(
+    app: AppHandle,
     text: String,
     api_key: String,
 ) -> Result<ExtractedVocabulary, String> {
-    let client = Client::new();
+    let openai_base = load_api_endpoints(&app).openai_v1();
+    request_vocabulary_terms(&shared_client(&app), &openai_base, &EXTRACTION_RETRY, text, &api_key).await
+}
 
+/// Ask the chat completions API at `openai_base` (e.g. https://api.openai.com/v1)
+/// to pull domain-specific terms out of a document
+pub async fn request_vocabulary_terms(
+    client: &Client,
+    openai_base: &str,
+    retry: &RetryPolicy,
+    text: String,
+    api_key: &str,
+) -> Result<ExtractedVocabulary, String> {
     // Truncate if too long (OpenAI has token limits)
     let truncated = if text.len() > 60000 {
         text[..60000].to_string()
@@ -217,14 +239,15 @@ pub async fn extract_vocabulary_terms(
         ],
     };
 
-    let response = client
-        .post("https://api.openai.com/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+    let response = send_with_retry(retry, "OpenAI request", || {
+        client
+            .post(format!("{}/chat/completions", openai_base))
+            .header("Authorization", format!("Bearer {}", api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -0,0 +1,357 @@
+// src-tauri/src/commands/word_boost.rs
+// Picks which terms to send as word_boost when the merged vocabularies exceed the limit,
+// and tracks how often boosted terms actually turn up in transcripts
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri::Manager;
+use thiserror::Error;
+
+use super::transcribe::TranscriptionOptions;
+use super::vocabulary::load_vocabularies;
+
+/// Most terms sent as word_boost in one request
+pub const MAX_WORD_BOOST_TERMS: usize = 200;
+/// Longest phrase AssemblyAI accepts as a single boost term
+pub const MAX_WORD_BOOST_WORDS: usize = 6;
+
+// Terms typed in for this transcription outrank every vocabulary
+const USER_TERM_PRIORITY: i32 = i32::MAX;
+
+#[derive(Error, Debug)]
+pub enum WordBoostError {
+    #[error("Failed to access app data directory: {0}")]
+    DirectoryError(String),
+    #[error("Failed to read word boost stats: {0}")]
+    ReadError(String),
+    #[error("Failed to write word boost stats: {0}")]
+    WriteError(String),
+    #[error("Failed to load vocabularies: {0}")]
+    VocabularyError(String),
+}
+
+impl serde::Serialize for WordBoostError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Serializes updates to the stats file, which parallel chunk and track
+/// transcriptions record into at the same time
+#[derive(Default)]
+pub struct WordBoostStatsLock(Mutex<()>);
+
+/// A term competing for a word_boost slot
+#[derive(Debug, Clone)]
+pub struct BoostCandidate {
+    pub term: String,
+    /// Higher wins; user-typed terms beat any vocabulary
+    pub priority: i32,
+    /// Where the term came from ("custom" or a vocabulary name), for the report
+    pub source: String,
+}
+
+/// How often a term has been boosted and how often it was then heard
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TermStats {
+    pub boosted: u32,
+    pub hits: u32,
+}
+
+impl TermStats {
+    // Smoothed hit rate, so unseen terms sit in the middle (0.5) rather than at the bottom
+    fn hit_rate(&self) -> f64 {
+        (self.hits as f64 + 1.0) / (self.boosted as f64 + 2.0)
+    }
+}
+
+/// Persisted hit statistics, keyed by lowercased term
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct WordBoostStats {
+    #[serde(default)]
+    pub terms: HashMap<String, TermStats>,
+    /// Transcript ID -> terms boosted for it, until the transcript finishes
+    #[serde(default)]
+    pub pending: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DropReason {
+    /// Same term (ignoring case) already selected from a higher-ranked source
+    Duplicate,
+    /// More than six words
+    TooLong,
+    /// Ranked below the cut-off
+    OverLimit,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DroppedTerm {
+    pub term: String,
+    pub source: String,
+    pub reason: DropReason,
+}
+
+/// Result of the ranking step: the terms that will be sent, and what was left out and why
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WordBoostSelection {
+    pub selected: Vec<String>,
+    pub dropped: Vec<DroppedTerm>,
+}
+
+/// Rank candidates and keep the best `limit`.
+/// Order: priority, then past hit rate, then original order. Duplicates keep
+/// the highest-ranked copy.
+pub fn select_word_boost(
+    candidates: Vec<BoostCandidate>,
+    stats: &WordBoostStats,
+    limit: usize,
+) -> WordBoostSelection {
+    let mut selection = WordBoostSelection::default();
+    
+    let mut ranked: Vec<(usize, BoostCandidate, f64)> = Vec::new();
+    for (index, mut candidate) in candidates.into_iter().enumerate() {
+        let words: Vec<&str> = candidate.term.split_whitespace().collect();
+        if words.is_empty() {
+            continue;
+        }
+        let too_long = words.len() > MAX_WORD_BOOST_WORDS;
+        candidate.term = words.join(" ");
+        if too_long {
+            selection.dropped.push(DroppedTerm {
+                term: candidate.term,
+                source: candidate.source,
+                reason: DropReason::TooLong,
+            });
+            continue;
+        }
+        let hit_rate = stats
+            .terms
+            .get(&candidate.term.to_lowercase())
+            .map(TermStats::hit_rate)
+            .unwrap_or(0.5);
+        ranked.push((index, candidate, hit_rate));
+    }
+    
+    ranked.sort_by(|(a_index, a, a_rate), (b_index, b, b_rate)| {
+        b.priority
+            .cmp(&a.priority)
+            .then(b_rate.total_cmp(a_rate))
+            .then(a_index.cmp(b_index))
+    });
+    
+    let mut seen: HashSet<String> = HashSet::new();
+    for (_, candidate, _) in ranked {
+        let reason = if !seen.insert(candidate.term.to_lowercase()) {
+            DropReason::Duplicate
+        } else if selection.selected.len() >= limit {
+            DropReason::OverLimit
+        } else {
+            selection.selected.push(candidate.term);
+            continue;
+        };
+        selection.dropped.push(DroppedTerm {
+            term: candidate.term,
+            source: candidate.source,
+            reason,
+        });
+    }
+    
+    selection
+}
+
+// Get the stats file path
+fn get_stats_file(app: &AppHandle) -> Result<PathBuf, WordBoostError> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| WordBoostError::DirectoryError(e.to_string()))?;
+    
+    // Create directory if it doesn't exist
+    if !app_data_dir.exists() {
+        fs::create_dir_all(&app_data_dir)
+            .map_err(|e| WordBoostError::DirectoryError(e.to_string()))?;
+        info!("Created app data directory: {:?}", app_data_dir);
+    }
+    
+    Ok(app_data_dir.join("word_boost_stats.json"))
+}
+
+fn load_stats(app: &AppHandle) -> Result<WordBoostStats, WordBoostError> {
+    let stats_file = get_stats_file(app)?;
+    
+    if !stats_file.exists() {
+        return Ok(WordBoostStats::default());
+    }
+    
+    let content = fs::read_to_string(&stats_file)
+        .map_err(|e| WordBoostError::ReadError(e.to_string()))?;
+    
+    serde_json::from_str(&content)
+        .map_err(|e| WordBoostError::ReadError(e.to_string()))
+}
+
+fn save_stats(app: &AppHandle, stats: &WordBoostStats) -> Result<(), WordBoostError> {
+    let stats_file = get_stats_file(app)?;
+    
+    let content = serde_json::to_string_pretty(stats)
+        .map_err(|e| WordBoostError::WriteError(e.to_string()))?;
+    
+    // Write to a temp file first so a crash mid-write can't corrupt the stats
+    let tmp_file = stats_file.with_extension("json.tmp");
+    fs::write(&tmp_file, content)
+        .map_err(|e| WordBoostError::WriteError(e.to_string()))?;
+    fs::rename(&tmp_file, &stats_file)
+        .map_err(|e| WordBoostError::WriteError(e.to_string()))
+}
+
+// Load, change and save the stats while holding the lock, so concurrent updates
+// don't overwrite each other. `update` returns whether anything changed.
+fn update_stats<F>(app: &AppHandle, update: F) -> Result<(), WordBoostError>
+where
+    F: FnOnce(&mut WordBoostStats) -> bool,
+{
+    let lock = app.try_state::<WordBoostStatsLock>();
+    let _guard = lock.as_ref().map(|l| l.0.lock().unwrap());
+    
+    let mut stats = load_stats(app)?;
+    if update(&mut stats) {
+        save_stats(app, &stats)?;
+    }
+    Ok(())
+}
+
+/// Gather the typed-in terms and the selected vocabularies' terms, then rank them
+pub async fn resolve_word_boost(
+    app: &AppHandle,
+    options: &TranscriptionOptions,
+) -> Result<WordBoostSelection, WordBoostError> {
+    let mut candidates: Vec<BoostCandidate> = options
+        .boost_words
+        .iter()
+        .map(|term| BoostCandidate {
+            term: term.clone(),
+            priority: USER_TERM_PRIORITY,
+            source: "custom".to_string(),
+        })
+        .collect();
+    
+    if !options.vocabulary_ids.is_empty() {
+        let data = load_vocabularies(app.clone())
+            .await
+            .map_err(|e| WordBoostError::VocabularyError(e.to_string()))?;
+    
+        // Keep the order the vocabularies were selected in as the final tie-breaker
+        for id in &options.vocabulary_ids {
+            match data.vocabularies.iter().find(|v| &v.id == id) {
+                Some(vocab) => candidates.extend(vocab.terms.iter().map(|term| BoostCandidate {
+                    term: term.clone(),
+                    priority: vocab.priority,
+                    source: vocab.name.clone(),
+                })),
+                None => warn!("Selected vocabulary {} no longer exists", id),
+            }
+        }
+    }
+    
+    let stats = load_stats(app).unwrap_or_else(|e| {
+        warn!("Ranking word boost without hit stats: {}", e);
+        WordBoostStats::default()
+    });
+    
+    Ok(select_word_boost(candidates, &stats, MAX_WORD_BOOST_TERMS))
+}
+
+/// Resolve the word_boost list for a submission, logging anything left out
+pub async fn apply_word_boost(
+    app: &AppHandle,
+    options: &TranscriptionOptions,
+) -> Result<TranscriptionOptions, WordBoostError> {
+    let selection = resolve_word_boost(app, options).await?;
+    
+    if !selection.dropped.is_empty() {
+        info!(
+            "Word boost: sending {} term(s), dropped {}",
+            selection.selected.len(),
+            selection.dropped.len()
+        );
+    }
+    
+    let mut resolved = options.clone();
+    resolved.boost_words = selection.selected;
+    resolved.vocabulary_ids.clear();
+    Ok(resolved)
+}
+
+/// Remember which terms were boosted for a transcript, so hits can be counted when it completes
+pub fn track_boosted_terms(app: &AppHandle, transcript_id: &str, terms: &[String]) {
+    if terms.is_empty() {
+        return;
+    }
+    let result = update_stats(app, |stats| {
+        stats.pending.insert(transcript_id.to_string(), terms.to_vec());
+        true
+    });
+    if let Err(e) = result {
+        warn!("Failed to track boosted terms for {}: {}", transcript_id, e);
+    }
+}
+
+/// Count which boosted terms appear in a finished transcript's text.
+/// `text` is None for a failed transcript, which just forgets the pending terms.
+pub fn record_boost_hits(app: &AppHandle, transcript_id: &str, text: Option<&str>) {
+    let result = update_stats(app, |stats| {
+        let Some(terms) = stats.pending.remove(transcript_id) else {
+            return false;
+        };
+        if let Some(text) = text {
+            let text = text.to_lowercase();
+            for term in terms {
+                let term = term.to_lowercase();
+                let heard = contains_phrase(&text, &term);
+                let entry = stats.terms.entry(term).or_default();
+                entry.boosted += 1;
+                if heard {
+                    entry.hits += 1;
+                }
+            }
+        }
+        true
+    });
+    if let Err(e) = result {
+        warn!("Failed to record word boost hits for {}: {}", transcript_id, e);
+    }
+}
+
+// Whole-word match, so "MS" doesn't count as heard inside "symptoms"
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    text.match_indices(phrase).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + phrase.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Show which terms would be sent for these options, and which would be dropped and why
+#[tauri::command]
+pub async fn preview_word_boost(
+    app: AppHandle,
+    options: TranscriptionOptions,
+) -> Result<WordBoostSelection, WordBoostError> {
+    resolve_word_boost(&app, &options).await
+}
+
+/// Hit statistics for every term boosted so far
+#[tauri::command]
+pub async fn get_word_boost_stats(app: AppHandle) -> Result<HashMap<String, TermStats>, WordBoostError> {
+    Ok(load_stats(&app)?.terms)
+}
@@ -0,0 +1,222 @@
+// src-tauri/src/http.rs
+// Shared outbound HTTP layer: one app-wide client configured from the network
+// settings (proxy, extra root CAs, timeouts), plus retries of transient failures
+// with exponential backoff and jitter, honouring Retry-After on 429/503 responses
+
+use log::{info, warn};
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Certificate, Client, Proxy, RequestBuilder, Response, StatusCode};
+use std::fs;
+use std::sync::RwLock;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use thiserror::Error;
+
+use crate::commands::settings::NetworkSettings;
+
+/// Connect timeout used when the settings don't specify one
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error("{label} failed: {message} (after {attempts} attempt(s))")]
+    RequestFailed { label: String, message: String, attempts: u32 },
+    #[error("{label} failed with status {status}: {body} (after {attempts} attempt(s))")]
+    RetriesExhausted { label: String, status: StatusCode, body: String, attempts: u32 },
+    #[error("Invalid network settings: {0}")]
+    InvalidConfig(String),
+}
+
+/// The app-wide HTTP client, kept in Tauri state so connections are reused.
+/// Swapped out when the network settings change.
+pub struct HttpClient(RwLock<Client>);
+
+impl HttpClient {
+    pub fn new(client: Client) -> Self {
+        Self(RwLock::new(client))
+    }
+
+    /// A handle to the current client (cheap: clients share their connection pool)
+    pub fn get(&self) -> Client {
+        self.0.read().unwrap().clone()
+    }
+
+    pub fn replace(&self, client: Client) {
+        *self.0.write().unwrap() = client;
+    }
+}
+
+/// Fetch the shared client from app state
+pub fn shared_client(app: &AppHandle) -> Client {
+    app.state::<HttpClient>().get()
+}
+
+/// Build a client honouring the proxy, extra root certificates and timeouts in `network`
+pub fn build_client(network: &NetworkSettings) -> Result<Client, HttpError> {
+    let connect_timeout = network.connect_timeout_secs.unwrap_or(DEFAULT_CONNECT_TIMEOUT_SECS);
+    let mut builder = Client::builder().connect_timeout(Duration::from_secs(connect_timeout));
+    
+    // No overall request timeout: large uploads can legitimately take a long time,
+    // so only a stall between reads is treated as a failure
+    if let Some(secs) = network.read_timeout_secs {
+        builder = builder.read_timeout(Duration::from_secs(secs));
+    }
+    
+    if let Some(proxy_url) = non_empty(&network.proxy_url) {
+        let mut proxy = Proxy::all(proxy_url)
+            .map_err(|e| HttpError::InvalidConfig(format!("Bad proxy URL {}: {}", proxy_url, e)))?;
+        if let Some(username) = non_empty(&network.proxy_username) {
+            proxy = proxy.basic_auth(username, network.proxy_password.as_deref().unwrap_or_default());
+        }
+        info!("Routing outbound requests through proxy {}", proxy_url);
+        builder = builder.proxy(proxy);
+    }
+    
+    for path in &network.ca_certificates {
+        let pem = fs::read(path)
+            .map_err(|e| HttpError::InvalidConfig(format!("Failed to read CA certificate {}: {}", path, e)))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .map_err(|e| HttpError::InvalidConfig(format!("Failed to parse CA certificate {}: {}", path, e)))?;
+        if certs.is_empty() {
+            return Err(HttpError::InvalidConfig(format!("No PEM certificates found in {}", path)));
+        }
+        info!("Trusting {} extra root certificate(s) from {}", certs.len(), path);
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    
+    builder
+        .build()
+        .map_err(|e| HttpError::InvalidConfig(e.to_string()))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// How hard to retry a request before giving up
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one (1 = no retries)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each subsequent retry
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including server-requested ones
+    pub max_delay: Duration,
+    /// Whether repeating the request is harmless. When it isn't, only failures that
+    /// show the request wasn't acted on are retried: no connection, 429 and 5xx.
+    pub idempotent: bool,
+}
+
+impl RetryPolicy {
+    pub const fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self { max_attempts, base_delay, max_delay, idempotent: true }
+    }
+
+    /// For requests that create something (e.g. submitting a transcript): a timeout may
+    /// mean the server already accepted the first attempt, so it isn't retried
+    pub const fn non_idempotent(self) -> Self {
+        Self { idempotent: false, ..self }
+    }
+
+    /// Send once, never retry
+    pub const fn none() -> Self {
+        Self::new(1, Duration::ZERO, Duration::ZERO)
+    }
+
+    // Exponential backoff with jitter: a random delay in [d/2, d] where d = base * 2^(retry - 1)
+    fn backoff(&self, retry: u32) -> Duration {
+        let exp = self.base_delay.saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let capped = exp.min(self.max_delay);
+        let half = capped / 2;
+        half + capped.saturating_sub(half).mul_f64(rand::thread_rng().gen::<f64>())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(500), Duration::from_secs(30))
+    }
+}
+
+// Statuses worth trying again: rate limiting and server-side trouble
+fn is_retryable_status(status: StatusCode, policy: &RetryPolicy) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::REQUEST_TIMEOUT && policy.idempotent)
+        || matches!(status.as_u16(), 500 | 502 | 503 | 504)
+}
+
+// Connection problems and timeouts are transient; bad URLs or bodies are not.
+// Only a failed connect is sure not to have reached the server.
+fn is_retryable_error(err: &reqwest::Error, policy: &RetryPolicy) -> bool {
+    err.is_connect() || (policy.idempotent && (err.is_timeout() || err.is_request()))
+}
+
+/// Parse a Retry-After header given either as seconds or as an HTTP date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let when = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = when.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Send a request, retrying transient failures according to `policy`.
+///
+/// `build` is called once per attempt, since a request (and a streamed body)
+/// can only be sent once. Non-retryable responses such as 400 or 401 are handed
+/// back to the caller as-is; if retryable failures use up the budget, the error
+/// says how many attempts were made.
+pub async fn send_with_retry<F>(
+    policy: &RetryPolicy,
+    label: &str,
+    mut build: F,
+) -> Result<Response, HttpError>
+where
+    F: FnMut() -> RequestBuilder,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 0;
+    
+    loop {
+        attempt += 1;
+        let last_attempt = attempt >= max_attempts;
+    
+        match build().send().await {
+            Ok(response) if is_retryable_status(response.status(), policy) => {
+                let status = response.status();
+                if last_attempt {
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(HttpError::RetriesExhausted {
+                        label: label.to_string(),
+                        status,
+                        body,
+                        attempts: attempt,
+                    });
+                }
+                let delay = retry_after(&response)
+                    .unwrap_or_else(|| policy.backoff(attempt))
+                    .min(policy.max_delay);
+                warn!("{} returned {} (attempt {}/{}), retrying in {:?}", label, status, attempt, max_attempts, delay);
+                tokio::time::sleep(delay).await;
+            }
+            Ok(response) => return Ok(response),
+            Err(e) if !last_attempt && is_retryable_error(&e, policy) => {
+                let delay = policy.backoff(attempt);
+                warn!("{} failed (attempt {}/{}): {}, retrying in {:?}", label, attempt, max_attempts, e, delay);
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                return Err(HttpError::RequestFailed {
+                    label: label.to_string(),
+                    message: e.to_string(),
+                    attempts: attempt,
+                });
+            }
+        }
+    }
+}
@@ -1,17 +1,42 @@
-mod commands;
+pub mod commands;
+pub mod http;
+pub mod providers;
+pub mod temp_workspace;
 
 use commands::audio::{store_audio_file, delete_audio_file};
-use commands::convert::{cleanup_temp_dir, convert_to_audio};
+use commands::chunked::transcribe_chunks;
+use commands::convert::{cleanup_temp_dir, convert_mixdown, convert_session, convert_to_audio, list_cleanup_profiles};
+use commands::job_queue::{list_transcription_jobs, dismiss_transcription_job};
 use commands::history::{save_history_entry, get_history_list, get_history_entry, delete_history_entry};
 use commands::lemur::identify_speakers;
+use commands::local_transcribe::transcribe_locally;
+use commands::media_info::inspect_media;
+use commands::multitrack::transcribe_tracks;
 use commands::presets::{save_preset, get_presets, delete_preset};
-use commands::settings::{delete_api_key, get_api_key, set_api_key, get_openai_key, set_openai_key};
-use commands::transcribe::{poll_transcription, submit_transcription, upload_audio};
+use commands::retention::{delete_remote_transcript, list_remote_deletions, DeletionLogLock};
+use commands::review::get_low_confidence_spans;
+use commands::settings::{
+    delete_api_key, get_api_key, set_api_key, get_openai_key, set_openai_key,
+    get_api_endpoints, set_api_endpoints, get_network_settings, set_network_settings,
+    load_network_settings, get_auto_delete_remote, set_auto_delete_remote,
+    get_whisper_model_path, set_whisper_model_path
+};
+use commands::speaker_roles::{list_role_sets, save_role_set, delete_role_set};
+use commands::timeline::{locate_session_sources, remap_transcript};
+use commands::transcribe::{
+    poll_transcription, submit_transcription, upload_audio, cancel_upload, ActiveUploads,
+    wait_for_transcription, cancel_transcription_wait, ActiveWaits,
+    start_transcription_job, get_transcription_job, resume_transcription_jobs, TranscriptionJobs
+};
 use commands::vocabulary::{
     load_vocabularies, create_vocabulary, update_vocabulary, delete_vocabulary,
     duplicate_vocabulary, create_vocabulary_category, export_vocabularies, import_vocabularies
 };
 use commands::vocabulary_extract::{extract_document_text, extract_vocabulary_terms};
+use commands::word_boost::{preview_word_boost, get_word_boost_stats, WordBoostStatsLock};
+use http::{build_client, HttpClient};
+use tauri::{Manager, RunEvent};
+use temp_workspace::{init_temp_workspaces, TempWorkspaces};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -29,9 +54,33 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .manage(TranscriptionJobs::default())
+        .manage(ActiveUploads::default())
+        .manage(ActiveWaits::default())
+        .manage(WordBoostStatsLock::default())
+        .manage(DeletionLogLock::default())
+        .setup(|app| {
+            // One HTTP client for every outbound request, configured from the network settings
+            let client = build_client(&load_network_settings(app.handle())).unwrap_or_else(|e| {
+                log::error!("{}; falling back to default network settings", e);
+                reqwest::Client::new()
+            });
+            app.manage(HttpClient::new(client));
+    
+            // Converted audio lives here until its job finishes; clears leftovers from crashed runs
+            app.manage(init_temp_workspaces(app.handle()));
+    
+            // Pick up transcriptions that were still running when the app last closed
+            resume_transcription_jobs(app.handle());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // FFmpeg conversion
+            inspect_media,
             convert_to_audio,
+            convert_session,
+            convert_mixdown,
+            list_cleanup_profiles,
             cleanup_temp_dir,
             // Audio storage
             store_audio_file,
@@ -42,10 +91,35 @@ pub fn run() {
             delete_api_key,
             get_openai_key,
             set_openai_key,
+            get_api_endpoints,
+            set_api_endpoints,
+            get_network_settings,
+            set_network_settings,
+            get_auto_delete_remote,
+            set_auto_delete_remote,
+            get_whisper_model_path,
+            set_whisper_model_path,
             // AssemblyAI
             upload_audio,
+            cancel_upload,
             submit_transcription,
             poll_transcription,
+            wait_for_transcription,
+            cancel_transcription_wait,
+            transcribe_chunks,
+            transcribe_tracks,
+            remap_transcript,
+            locate_session_sources,
+            // Local transcription (whisper.cpp)
+            transcribe_locally,
+            // Backend transcription jobs
+            start_transcription_job,
+            get_transcription_job,
+            list_transcription_jobs,
+            dismiss_transcription_job,
+            // Data retention
+            delete_remote_transcript,
+            list_remote_deletions,
             // LeMUR AI
             identify_speakers,
             // History
@@ -66,10 +140,24 @@ pub fn run() {
             create_vocabulary_category,
             export_vocabularies,
             import_vocabularies,
+            // Speaker role sets
+            list_role_sets,
+            save_role_set,
+            delete_role_set,
+            // Transcript review
+            get_low_confidence_spans,
+            // Word boost ranking
+            preview_word_boost,
+            get_word_boost_stats,
             // Vocabulary extraction
             extract_document_text,
             extract_vocabulary_terms,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                app.state::<TempWorkspaces>().release_all();
+            }
+        });
 }
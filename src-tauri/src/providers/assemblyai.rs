@@ -0,0 +1,487 @@
+// src-tauri/src/providers/assemblyai.rs
+// AssemblyAI implementation of TranscriptionProvider
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{stream, StreamExt};
+use log::{info, error};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+
+use super::{
+    Chapter, Entity, IabCategoriesResult, SentimentResult, TranscriptResponse, TranscriptStatus,
+    TranscriptionProvider, UploadProgressFn, Utterance,
+};
+use crate::commands::transcribe::{CustomSpelling, SpeakerLabelMode, TranscribeError, TranscriptionOptions};
+use crate::commands::word_boost::MAX_WORD_BOOST_TERMS;
+use crate::http::{send_with_retry, RetryPolicy};
+
+// Size of each chunk read from disk while streaming an upload
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Response from upload endpoint
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    upload_url: String,
+}
+
+/// Speaker identification configuration
+#[derive(Debug, Serialize, Clone)]
+struct SpeakerIdentification {
+    speaker_type: String,  // "role" or "name"
+    known_values: Vec<String>,
+}
+
+/// Speech understanding request wrapper
+#[derive(Debug, Serialize, Clone)]
+struct SpeechUnderstandingRequest {
+    speaker_identification: SpeakerIdentification,
+}
+
+/// Speech understanding wrapper
+#[derive(Debug, Serialize, Clone)]
+struct SpeechUnderstanding {
+    request: SpeechUnderstandingRequest,
+}
+
+/// Request body for transcript submission
+#[derive(Debug, Serialize)]
+struct TranscriptRequest {
+    audio_url: String,
+    speaker_labels: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speakers_expected: Option<i32>,  // Used when exact count known
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    word_boost: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    summarization: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary_type: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    iab_categories: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    sentiment_analysis: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    auto_highlights: bool,  // Key phrases extraction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speech_understanding: Option<SpeechUnderstanding>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_code: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    language_detection: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    redact_pii: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    redact_pii_policies: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    auto_chapters: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    entity_detection: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    filter_profanity: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    disfluencies: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    boost_param: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    custom_spelling: Vec<CustomSpelling>,
+}
+
+/// Transcript as returned by AssemblyAI's submit and poll endpoints
+/// Using flatten to capture any extra fields we don't explicitly handle
+#[derive(Debug, Deserialize)]
+struct AssemblyAiTranscript {
+    id: String,
+    status: TranscriptStatus,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    utterances: Option<Vec<Utterance>>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    iab_categories_result: Option<IabCategoriesResult>,
+    #[serde(default)]
+    sentiment_analysis_results: Option<Vec<SentimentResult>>,
+    #[serde(default)]
+    language_code: Option<String>,
+    #[serde(default)]
+    language_confidence: Option<f64>,
+    #[serde(default)]
+    chapters: Option<Vec<Chapter>>,
+    #[serde(default)]
+    entities: Option<Vec<Entity>>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+}
+
+impl From<AssemblyAiTranscript> for TranscriptResponse {
+    fn from(t: AssemblyAiTranscript) -> Self {
+        TranscriptResponse {
+            id: t.id,
+            status: t.status,
+            text: t.text,
+            utterances: t.utterances,
+            summary: t.summary,
+            iab_categories_result: t.iab_categories_result,
+            sentiment_analysis_results: t.sentiment_analysis_results,
+            language_code: t.language_code,
+            language_confidence: t.language_confidence,
+            chapters: t.chapters,
+            entities: t.entities,
+            error: t.error,
+            extra: t.extra,
+        }
+    }
+}
+
+// Build speech_understanding config based on speaker label mode
+fn speech_understanding_for(options: &TranscriptionOptions) -> Option<SpeechUnderstanding> {
+    let (speaker_type, known_values) = match options.speaker_label_mode {
+        SpeakerLabelMode::Generic => return None,  // No speaker identification
+        SpeakerLabelMode::AutoNames => ("name", vec![]),  // Empty = auto-detect names from conversation
+        SpeakerLabelMode::KnownNames => ("name", options.speaker_values.clone()),
+        SpeakerLabelMode::CustomRoles | SpeakerLabelMode::RoleSet => ("role", options.speaker_values.clone()),
+        mode @ (SpeakerLabelMode::Interview | SpeakerLabelMode::Podcast | SpeakerLabelMode::Panel) => {
+            ("role", mode.preset_roles().iter().map(|r| r.to_string()).collect())
+        }
+    };
+    
+    Some(SpeechUnderstanding {
+        request: SpeechUnderstandingRequest {
+            speaker_identification: SpeakerIdentification {
+                speaker_type: speaker_type.to_string(),
+                known_values,
+            },
+        },
+    })
+}
+
+// Reject option combinations AssemblyAI would refuse, before anything is sent
+fn check_options(options: &TranscriptionOptions) -> Result<(), TranscribeError> {
+    if options.redact_pii && options.redact_pii_policies.is_empty() {
+        return Err(TranscribeError::InvalidOptions("PII redaction needs at least one policy".to_string()));
+    }
+    if options.auto_chapters && options.include_summary {
+        return Err(TranscribeError::InvalidOptions("Chapters and summary can't be enabled together".to_string()));
+    }
+    if options.language_detection && options.language_code.is_some() {
+        return Err(TranscribeError::InvalidOptions("Set a language or enable language detection, not both".to_string()));
+    }
+    // Saved role sets are swapped for their roles before submitting (see speaker_roles.rs)
+    if options.speaker_label_mode == SpeakerLabelMode::RoleSet && options.speaker_values.is_empty() {
+        return Err(TranscribeError::InvalidOptions("Role set has not been resolved to roles".to_string()));
+    }
+    if let Some(boost_param) = &options.boost_param {
+        if !matches!(boost_param.as_str(), "low" | "default" | "high") {
+            return Err(TranscribeError::InvalidOptions(format!("Unknown boost weight: {}", boost_param)));
+        }
+    }
+    // Callers rank the list down first (see word_boost.rs) rather than losing whatever came last
+    if options.boost_words.len() > MAX_WORD_BOOST_TERMS {
+        return Err(TranscribeError::InvalidOptions(format!(
+            "Too many boost words ({}, max {})",
+            options.boost_words.len(),
+            MAX_WORD_BOOST_TERMS
+        )));
+    }
+    check_custom_spelling(&options.custom_spelling)
+}
+
+// `to` must be a single word; `from` phrases are case-insensitive, so one phrase
+// can't be rewritten to two different words
+fn check_custom_spelling(rules: &[CustomSpelling]) -> Result<(), TranscribeError> {
+    let invalid = |msg: String| Err(TranscribeError::InvalidOptions(msg));
+    
+    let mut targets: HashMap<String, &str> = HashMap::new();
+    for rule in rules {
+        let to = rule.to.trim();
+        if to.is_empty() || to.split_whitespace().count() > 1 {
+            return invalid(format!("Custom spelling must map to a single word, got \"{}\"", rule.to));
+        }
+        if rule.from.is_empty() {
+            return invalid(format!("Custom spelling for \"{}\" has no variants", to));
+        }
+        for from in &rule.from {
+            if from.trim().is_empty() {
+                return invalid(format!("Custom spelling for \"{}\" has an empty variant", to));
+            }
+            let key = from.trim().to_lowercase();
+            match targets.get(&key) {
+                Some(existing) if *existing != to => {
+                    return invalid(format!("\"{}\" is mapped to both \"{}\" and \"{}\"", from, existing, to));
+                }
+                _ => {
+                    targets.insert(key, to);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// Stream a file from disk as a request body, reporting bytes as they are read
+fn file_body(path: &Path, total_bytes: u64, on_progress: UploadProgressFn) -> reqwest::Body {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => File::from_std(file),
+        Err(e) => return reqwest::Body::wrap_stream(stream::once(async move { Err::<Bytes, _>(e) })),
+    };
+    
+    let mut bytes_sent: u64 = 0;
+    let stream = ReaderStream::with_capacity(file, UPLOAD_CHUNK_SIZE).inspect(move |chunk| {
+        if let Ok(bytes) = chunk {
+            bytes_sent += bytes.len() as u64;
+            on_progress(bytes_sent, total_bytes);
+        }
+    });
+    reqwest::Body::wrap_stream(stream)
+}
+
+/// Talks to the AssemblyAI v2 REST API
+pub struct AssemblyAiProvider {
+    client: reqwest::Client,
+    api_base: String,
+    api_key: String,
+    retry: RetryPolicy,
+}
+
+impl AssemblyAiProvider {
+    /// `api_base` is the v2 root, e.g. https://api.assemblyai.com/v2
+    pub fn new(client: reqwest::Client, api_base: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            client,
+            api_base: api_base.into(),
+            api_key: api_key.into(),
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Use a different retry budget for every request this provider makes
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    // Parse a transcript body, logging the raw text if it doesn't match
+    fn parse_transcript(response_text: &str, what: &str) -> Result<TranscriptResponse, TranscribeError> {
+        let transcript: AssemblyAiTranscript = serde_json::from_str(response_text)
+            .map_err(|e| {
+                error!("Failed to parse {}: {}. Response was: {}", what, e, &response_text.chars().take(1000).collect::<String>());
+                TranscribeError::RequestFailed(format!("Failed to parse {}: {}", what, e))
+            })?;
+        Ok(transcript.into())
+    }
+}
+
+#[async_trait]
+impl TranscriptionProvider for AssemblyAiProvider {
+    fn name(&self) -> &'static str {
+        "AssemblyAI"
+    }
+
+    /// AssemblyAI expects the raw audio bytes in the request body, not multipart form.
+    /// The file is streamed from disk in chunks rather than read into memory.
+    async fn upload(&self, file_path: &Path, on_progress: UploadProgressFn) -> Result<String, TranscribeError> {
+        // Find the file size for progress reporting
+        info!("Opening file...");
+        let total_bytes = tokio::fs::metadata(file_path)
+            .await
+            .map_err(|e| {
+                error!("Failed to open file: {}", e);
+                TranscribeError::FileError(format!("Failed to open file: {}", e))
+            })?
+            .len();
+        
+        info!("File opened successfully, size: {} bytes", total_bytes);
+        
+        // Log API key presence (not the actual key!)
+        info!("API key present: {}, length: {}", !self.api_key.is_empty(), self.api_key.len());
+        
+        // AssemblyAI upload endpoint expects raw bytes in body with Content-Type header
+        // Each attempt re-opens the file, since a streamed body can only be sent once
+        info!("Uploading to AssemblyAI...");
+        let response = send_with_retry(&self.retry, "Upload request", || {
+            self.client
+                .post(format!("{}/upload", self.api_base))
+                .header("Authorization", &self.api_key)
+                .header("Content-Type", "application/octet-stream")
+                .header("Content-Length", total_bytes)
+                .body(file_body(file_path, total_bytes, on_progress.clone()))
+        })
+        .await
+        .map_err(|e| {
+            error!("{}", e);
+            TranscribeError::from(e)
+        })?;
+        
+        let status = response.status();
+        info!("Upload response status: {}", status);
+        
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            error!("Upload failed with status {}: {}", status, text);
+            return Err(TranscribeError::ApiError(format!(
+                "Upload failed with status {}: {}",
+                status, text
+            )));
+        }
+        
+        let upload_response: UploadResponse = response
+            .json()
+            .await
+            .map_err(|e| {
+                error!("Failed to parse upload response: {}", e);
+                TranscribeError::RequestFailed(format!("Failed to parse upload response: {}", e))
+            })?;
+        
+        info!("Upload successful! URL: {}", upload_response.upload_url);
+        Ok(upload_response.upload_url)
+    }
+
+    async fn submit(&self, audio_url: &str, options: &TranscriptionOptions) -> Result<String, TranscribeError> {
+        check_options(options)?;
+        let speech_understanding = speech_understanding_for(options);
+        
+        if speech_understanding.is_some() {
+            info!("Speaker identification enabled with mode: {:?}", options.speaker_label_mode);
+        }
+        
+        let request = TranscriptRequest {
+            audio_url: audio_url.to_string(),
+            speaker_labels: true,
+            speakers_expected: options.max_speakers,  // Use as hint for max speakers
+            word_boost: options.boost_words.clone(),
+            summarization: options.include_summary,
+            summary_model: if options.include_summary { Some("informative".to_string()) } else { None },
+            summary_type: if options.include_summary { Some("bullets".to_string()) } else { None },
+            iab_categories: options.detect_topics,
+            sentiment_analysis: options.analyze_sentiment,
+            auto_highlights: options.extract_key_phrases,
+            speech_understanding,
+            language_code: options.language_code.clone(),
+            language_detection: options.language_detection,
+            redact_pii: options.redact_pii,
+            redact_pii_policies: if options.redact_pii { options.redact_pii_policies.clone() } else { vec![] },
+            auto_chapters: options.auto_chapters,
+            entity_detection: options.entity_detection,
+            filter_profanity: options.filter_profanity,
+            disfluencies: options.disfluencies,
+            // Only meaningful alongside boosted words
+            boost_param: options.boost_param.clone().filter(|_| !options.boost_words.is_empty()),
+            custom_spelling: options.custom_spelling.clone(),
+        };
+        
+        if !request.custom_spelling.is_empty() {
+            info!("Applying {} custom spelling rule(s)", request.custom_spelling.len());
+        }
+        
+        info!("Request: {:?}", request);
+        
+        let response = send_with_retry(&self.retry, "Transcription request", || {
+            self.client
+                .post(format!("{}/transcript", self.api_base))
+                .header("Authorization", &self.api_key)
+                .header("Content-Type", "application/json")
+                .json(&request)
+        })
+        .await
+        .map_err(|e| {
+            error!("{}", e);
+            TranscribeError::from(e)
+        })?;
+        
+        let status = response.status();
+        info!("Transcription submit response status: {}", status);
+        
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            error!("Transcription submission failed with status {}: {}", status, text);
+            return Err(TranscribeError::ApiError(format!(
+                "Transcription submission failed with status {}: {}",
+                status, text
+            )));
+        }
+        
+        // Get raw response text first for debugging
+        let response_text = response.text().await.map_err(|e| {
+            error!("Failed to get response text: {}", e);
+            TranscribeError::RequestFailed(format!("Failed to get response text: {}", e))
+        })?;
+        
+        info!("Raw response (first 500 chars): {}", &response_text.chars().take(500).collect::<String>());
+        
+        let transcript = Self::parse_transcript(&response_text, "transcript response")?;
+        
+        info!("Transcription submitted! ID: {}", transcript.id);
+        Ok(transcript.id)
+    }
+
+    async fn poll(&self, transcript_id: &str) -> Result<TranscriptResponse, TranscribeError> {
+        let response = send_with_retry(&self.retry, "Poll request", || {
+            self.client
+                .get(format!("{}/transcript/{}", self.api_base, transcript_id))
+                .header("Authorization", &self.api_key)
+        })
+        .await
+        .map_err(|e| {
+            error!("{}", e);
+            TranscribeError::from(e)
+        })?;
+        
+        let status = response.status();
+        
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            error!("Poll request failed with status {}: {}", status, text);
+            return Err(TranscribeError::ApiError(format!(
+                "Poll request failed with status {}: {}",
+                status, text
+            )));
+        }
+        
+        // Get raw response text first for debugging
+        let response_text = response.text().await.map_err(|e| {
+            error!("Failed to get poll response text: {}", e);
+            TranscribeError::RequestFailed(format!("Failed to get poll response text: {}", e))
+        })?;
+        
+        Self::parse_transcript(&response_text, "poll response")
+    }
+
+    /// Deleting a transcript also removes the uploaded audio it was created from
+    async fn delete(&self, transcript_id: &str) -> Result<(), TranscribeError> {
+        info!("Deleting transcript from AssemblyAI: {}", transcript_id);
+        
+        let response = send_with_retry(&self.retry, "Delete request", || {
+            self.client
+                .delete(format!("{}/transcript/{}", self.api_base, transcript_id))
+                .header("Authorization", &self.api_key)
+        })
+        .await
+        .map_err(|e| {
+            error!("{}", e);
+            TranscribeError::from(e)
+        })?;
+        
+        let status = response.status();
+        
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            error!("Delete request failed with status {}: {}", status, text);
+            return Err(TranscribeError::ApiError(format!(
+                "Delete request failed with status {}: {}",
+                status, text
+            )));
+        }
+        
+        info!("Transcript deleted: {}", transcript_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+// src-tauri/src/providers/mod.rs
+// Transcription engines behind a common interface
+
+pub mod assemblyai;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::commands::transcribe::{TranscribeError, TranscriptionOptions};
+
+pub use assemblyai::AssemblyAiProvider;
+
+/// Callback for upload progress: (bytes sent, total bytes)
+pub type UploadProgressFn = Arc<dyn Fn(u64, u64) + Send + Sync>;
+
+/// A speech-to-text engine.
+/// Commands and the job runner only talk to this trait, so engines can be swapped
+/// (or faked in tests) without touching them.
+#[async_trait]
+pub trait TranscriptionProvider: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> &'static str;
+    
+    /// Make a local audio file available to the engine, returning a reference to it
+    /// (for AssemblyAI, the upload URL)
+    async fn upload(&self, file_path: &Path, on_progress: UploadProgressFn) -> Result<String, TranscribeError>;
+    
+    /// Start transcribing previously uploaded audio, returning the transcript ID
+    async fn submit(&self, audio_url: &str, options: &TranscriptionOptions) -> Result<String, TranscribeError>;
+    
+    /// Fetch the current state of a transcript
+    async fn poll(&self, transcript_id: &str) -> Result<TranscriptResponse, TranscribeError>;
+    
+    /// Remove a transcript (and its audio) from the engine's servers
+    async fn delete(&self, transcript_id: &str) -> Result<(), TranscribeError>;
+}
+
+/// Processing state of a transcript
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptStatus {
+    Queued,
+    Processing,
+    Completed,
+    Error,
+}
+
+/// Provider-neutral transcript
+/// Serializes to the shape the frontend's `TranscriptResponse` expects
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TranscriptResponse {
+    pub id: String,
+    pub status: TranscriptStatus,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub utterances: Option<Vec<Utterance>>,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub iab_categories_result: Option<IabCategoriesResult>,
+    #[serde(default)]
+    pub sentiment_analysis_results: Option<Vec<SentimentResult>>,
+    #[serde(default)]
+    pub language_code: Option<String>,
+    /// Confidence of automatic language detection (0-1)
+    #[serde(default)]
+    pub language_confidence: Option<f64>,
+    #[serde(default)]
+    pub chapters: Option<Vec<Chapter>>,
+    #[serde(default)]
+    pub entities: Option<Vec<Entity>>,
+    #[serde(default)]
+    pub error: Option<String>,
+    // Engine-specific fields we don't model explicitly
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Utterance {
+    pub speaker: String,
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+    /// Average word confidence, 0.0-1.0
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    #[serde(default)]
+    pub words: Vec<Word>,
+    /// For a session joined from several files, where the utterance starts in them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourcePosition>,
+}
+
+/// A moment of a joined session located in the file it was recorded in
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePosition {
+    /// Index of the file in the session
+    pub file: usize,
+    /// Time within that file
+    pub local_ms: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Word {
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+    /// How sure the model is of this word, 0.0-1.0
+    #[serde(default)]
+    pub confidence: Option<f64>,
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IabCategoriesResult {
+    #[serde(default)]
+    pub summary: HashMap<String, f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SentimentResult {
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+    pub sentiment: String, // "POSITIVE", "NEGATIVE", "NEUTRAL"
+    pub confidence: f64,
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+/// Auto-generated chapter covering a stretch of the recording
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Chapter {
+    pub headline: String,
+    pub gist: String,
+    pub summary: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Named entity found in the transcript
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Entity {
+    pub entity_type: String, // e.g. "person_name", "organization", "drug"
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+}
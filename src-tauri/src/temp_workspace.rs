@@ -0,0 +1,144 @@
+// src-tauri/src/temp_workspace.rs
+// Owns the temp directories that converted audio is written to, so they are removed when
+// a job finishes, when the app exits, and (after a crash) on the next launch
+
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+use tempfile::TempDir;
+use thiserror::Error;
+
+/// Leftover workspaces older than this are removed at startup. Younger ones may belong
+/// to another running instance.
+pub const STALE_WORKSPACE_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Error, Debug)]
+pub enum TempWorkspaceError {
+    #[error("Not a temp directory created by this app: {0}")]
+    NotManaged(String),
+    #[error("Failed to remove temp directory: {0}")]
+    RemoveFailed(#[from] std::io::Error),
+}
+
+impl serde::Serialize for TempWorkspaceError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Registry of live temp directories, kept in Tauri state.
+/// Every directory is created under one root, so anything left there by an earlier run
+/// can be found and swept.
+pub struct TempWorkspaces {
+    root: PathBuf,
+    dirs: Mutex<HashMap<PathBuf, TempDir>>,
+}
+
+impl TempWorkspaces {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            dirs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Create a directory that stays until `release` (or app exit)
+    pub fn create(&self) -> std::io::Result<PathBuf> {
+        let dir = self.scratch()?;
+        let path = dir.path().to_path_buf();
+        self.dirs.lock().unwrap().insert(path.clone(), dir);
+        Ok(path)
+    }
+
+    /// Create an untracked directory that is removed when the returned handle drops
+    pub fn scratch(&self) -> std::io::Result<TempDir> {
+        fs::create_dir_all(&self.root)?;
+        tempfile::Builder::new().prefix("job-").tempdir_in(&self.root)
+    }
+
+    pub fn is_managed(&self, path: &Path) -> bool {
+        self.dirs.lock().unwrap().contains_key(path)
+    }
+
+    /// Remove a directory returned by `create`. Any other path is refused.
+    pub fn release(&self, path: &Path) -> Result<(), TempWorkspaceError> {
+        let dir = self
+            .dirs
+            .lock()
+            .unwrap()
+            .remove(path)
+            .ok_or_else(|| TempWorkspaceError::NotManaged(path.display().to_string()))?;
+        dir.close()?;
+        Ok(())
+    }
+
+    /// Remove every tracked directory
+    pub fn release_all(&self) {
+        let dirs: Vec<TempDir> = self.dirs.lock().unwrap().drain().map(|(_, dir)| dir).collect();
+        for dir in dirs {
+            let path = dir.path().display().to_string();
+            if let Err(e) = dir.close() {
+                warn!("Failed to remove temp dir {}: {}", path, e);
+            }
+        }
+    }
+
+    /// Remove untracked directories under the root last modified more than `older_than` ago.
+    /// Returns how many were removed.
+    pub fn sweep_stale(&self, older_than: Duration) -> usize {
+        let Ok(entries) = fs::read_dir(&self.root) else {
+            return 0;
+        };
+        let now = SystemTime::now();
+        let mut removed = 0;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() || self.is_managed(&path) {
+                continue;
+            }
+            let age = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or_default();
+            if age < older_than {
+                continue;
+            }
+            match fs::remove_dir_all(&path) {
+                Ok(()) => removed += 1,
+                Err(e) => warn!("Failed to remove stale temp dir {:?}: {}", path, e),
+            }
+        }
+
+        removed
+    }
+}
+
+/// Set up the registry under the app's cache directory and clear out what earlier runs left
+pub fn init_temp_workspaces(app: &AppHandle) -> TempWorkspaces {
+    let root = app
+        .path()
+        .app_cache_dir()
+        .unwrap_or_else(|_| std::env::temp_dir().join("ohg-scribe"))
+        .join("conversions");
+    let workspaces = TempWorkspaces::new(root);
+
+    let removed = workspaces.sweep_stale(STALE_WORKSPACE_AGE);
+    if removed > 0 {
+        info!("Removed {} stale temp dir(s) from {:?}", removed, workspaces.root());
+    }
+    workspaces
+}
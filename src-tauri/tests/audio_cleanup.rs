@@ -0,0 +1,36 @@
+// Cleanup filter profiles applied while converting
+
+use ohg_scribe_lib::commands::convert::{audio_filter_args, AudioSelection, CleanupProfile};
+
+fn filters(profile: CleanupProfile) -> Vec<String> {
+    profile.filters().iter().map(|f| f.to_string()).collect()
+}
+
+#[test]
+fn profiles_are_named_in_kebab_case() {
+    let profile: CleanupProfile = serde_json::from_str("\"conference-room\"").unwrap();
+    assert_eq!(profile, CleanupProfile::ConferenceRoom);
+    assert_eq!(CleanupProfile::default(), CleanupProfile::None);
+    assert!(CleanupProfile::None.filters().is_empty());
+    assert!(serde_json::from_str::<CleanupProfile>("\"studio\"").is_err());
+}
+
+#[test]
+fn profile_filters_follow_the_selected_audio() {
+    // Default stream: a plain -af chain
+    let args = audio_filter_args(&AudioSelection::default(), &filters(CleanupProfile::Denoise)).unwrap();
+    assert_eq!(args, ["-af", "highpass=f=80,afftdn=nf=-25"]);
+
+    // One channel of a stream: the chain runs inside the filter graph, after the pick
+    let selection = AudioSelection { streams: vec![1], channel: Some(0) };
+    let args = audio_filter_args(&selection, &filters(CleanupProfile::Normalize)).unwrap();
+    assert_eq!(
+        args,
+        [
+            "-filter_complex",
+            "[0:1]pan=mono|c0=c0,loudnorm=I=-16:TP=-1.5:LRA=11[picked]",
+            "-map",
+            "[picked]"
+        ]
+    );
+}
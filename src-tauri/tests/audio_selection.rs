@@ -0,0 +1,30 @@
+// FFmpeg arguments for picking tracks and channels out of multi-track recordings
+
+use ohg_scribe_lib::commands::convert::{audio_selection_args, AudioSelection};
+
+fn args(streams: &[u32], channel: Option<u32>) -> Vec<String> {
+    audio_selection_args(&AudioSelection { streams: streams.to_vec(), channel }).unwrap()
+}
+
+#[test]
+fn streams_are_mapped_or_mixed() {
+    // The default leaves stream choice to FFmpeg
+    assert!(args(&[], None).is_empty());
+    assert_eq!(args(&[2], None), ["-map", "0:2"]);
+    assert_eq!(
+        args(&[3, 1, 3], None),
+        ["-filter_complex", "[0:1][0:3]amix=inputs=2:normalize=0[mixed]", "-map", "[mixed]"]
+    );
+}
+
+#[test]
+fn a_channel_is_taken_from_one_stream() {
+    assert_eq!(
+        args(&[1], Some(1)),
+        ["-filter_complex", "[0:1]pan=mono|c0=c1[picked]", "-map", "[picked]"]
+    );
+    assert_eq!(args(&[], Some(0))[1], "[0:a:0]pan=mono|c0=c0[picked]");
+
+    let mixed = AudioSelection { streams: vec![1, 2], channel: Some(0) };
+    assert!(audio_selection_args(&mixed).is_err());
+}
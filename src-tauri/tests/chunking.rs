@@ -0,0 +1,225 @@
+// Splitting long recordings at silences and stitching chunk transcripts back together
+
+mod common;
+
+use common::{MockServer, MOCK_API_KEY};
+use ohg_scribe_lib::commands::chunked::{merge_chunk_transcripts, run_parallel, ChunkTranscript};
+use ohg_scribe_lib::commands::convert::{parse_silence_log, plan_chunks, ChunkSpan, Silence, SplitOptions};
+use ohg_scribe_lib::commands::settings::ApiEndpoints;
+use ohg_scribe_lib::commands::transcribe::TranscribeError;
+use ohg_scribe_lib::providers::{AssemblyAiProvider, TranscriptResponse, TranscriptionProvider};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+const MINUTE: i64 = 60_000;
+
+fn silence_at(minute: f64) -> Silence {
+    let mid = (minute * MINUTE as f64) as i64;
+    Silence { start_ms: mid - 500, end_ms: mid + 500 }
+}
+
+fn provider_for(server: &MockServer) -> AssemblyAiProvider {
+    let endpoints = ApiEndpoints {
+        assemblyai: server.base_url(),
+        ..ApiEndpoints::default()
+    };
+    AssemblyAiProvider::new(reqwest::Client::new(), endpoints.assemblyai_v2(), MOCK_API_KEY)
+}
+
+fn write_chunks(dir: &tempfile::TempDir, count: usize) -> Vec<PathBuf> {
+    (0..count)
+        .map(|i| {
+            let path = dir.path().join(format!("chunk_{:03}.m4a", i));
+            std::fs::write(&path, vec![0u8; 4096]).unwrap();
+            path
+        })
+        .collect()
+}
+
+// Upload every path in parallel, two at a time
+async fn upload_all(
+    provider: &AssemblyAiProvider,
+    paths: Vec<PathBuf>,
+    cancel: &CancellationToken,
+) -> Result<Vec<String>, TranscribeError> {
+    run_parallel(paths, 2, cancel, |_, path| async move {
+        provider.upload(&path, Arc::new(|_, _| {})).await
+    })
+    .await
+}
+
+fn chunk(offset_ms: i64, duration_ms: i64, response: serde_json::Value) -> ChunkTranscript {
+    ChunkTranscript {
+        offset_ms,
+        duration_ms,
+        response: serde_json::from_value::<TranscriptResponse>(response).unwrap(),
+    }
+}
+
+#[test]
+fn silencedetect_log_gives_duration_and_silences() {
+    let stderr = "\
+Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'meeting.m4a':
+  Duration: 01:02:03.45, start: 0.000000, bitrate: 32 kb/s
+[silencedetect @ 0x7f9c] silence_start: -0.0015
+[silencedetect @ 0x7f9c] silence_end: 1.2 | silence_duration: 1.2015
+[silencedetect @ 0x7f9c] silence_start: 1805.25
+[silencedetect @ 0x7f9c] silence_end: 1806.5 | silence_duration: 1.25
+size=N/A time=01:02:03.45 bitrate=N/A speed= 812x";
+
+    let (duration_ms, silences) = parse_silence_log(stderr);
+    assert_eq!(duration_ms, Some(3_723_450));
+    assert_eq!(
+        silences,
+        vec![
+            Silence { start_ms: 0, end_ms: 1200 },
+            Silence { start_ms: 1_805_250, end_ms: 1_806_500 },
+        ]
+    );
+}
+
+#[test]
+fn chunks_are_cut_at_the_silence_nearest_the_target() {
+    let options = SplitOptions {
+        target_chunk_secs: 30 * 60,
+        max_chunk_secs: 40 * 60,
+        overlap_secs: 20,
+        ..SplitOptions::default()
+    };
+
+    // Short recordings stay whole
+    assert_eq!(plan_chunks(35 * MINUTE, &[], &options).len(), 1);
+
+    // 95 minutes: cut at the silence nearest 30 min, then nearest 60 min, rest stays whole
+    let silences = [silence_at(12.0), silence_at(28.0), silence_at(33.0), silence_at(61.0)];
+    let spans = plan_chunks(95 * MINUTE, &silences, &options);
+    let offsets: Vec<i64> = spans.iter().map(|s| s.offset_ms).collect();
+    assert_eq!(offsets, vec![0, 28 * MINUTE, 61 * MINUTE]);
+    // Every chunk but the last runs 20 s into the next
+    assert_eq!(spans[0], ChunkSpan { offset_ms: 0, duration_ms: 28 * MINUTE + 20_000 });
+    assert_eq!(spans[2].offset_ms + spans[2].duration_ms, 95 * MINUTE);
+
+    // With no silence in reach the cut falls at the maximum length
+    let spans = plan_chunks(50 * MINUTE, &[silence_at(5.0)], &options);
+    assert_eq!(spans[1].offset_ms, 40 * MINUTE);
+}
+
+#[test]
+fn chunk_transcripts_merge_onto_one_timeline_with_matched_speakers() {
+    // The first chunk runs 4 s past the cut at 10 s; the second starts at the cut
+    // and happens to label the two speakers the other way round
+    let first = chunk(0, 14_000, json!({
+        "id": "t1",
+        "status": "completed",
+        "summary": "- Opening remarks",
+        "utterances": [
+            { "speaker": "A", "text": "Welcome everyone.", "start": 1000, "end": 3000, "words": [
+                { "text": "Welcome", "start": 1000, "end": 2000, "confidence": 0.9 },
+                { "text": "everyone.", "start": 2000, "end": 3000, "confidence": 0.9 }
+            ]},
+            { "speaker": "B", "text": "Thanks. Glad to join today.", "start": 9000, "end": 13500, "words": [
+                { "text": "Thanks.", "start": 9000, "end": 9500, "confidence": 0.9 },
+                { "text": "Glad", "start": 10200, "end": 10600, "confidence": 0.9 },
+                { "text": "to", "start": 10600, "end": 10800, "confidence": 0.9 },
+                { "text": "join", "start": 10800, "end": 11200, "confidence": 0.9 },
+                { "text": "today.", "start": 11200, "end": 13500, "confidence": 0.9 }
+            ]}
+        ]
+    }));
+    let second = chunk(10_000, 8_000, json!({
+        "id": "t2",
+        "status": "completed",
+        "summary": "- Introductions",
+        "chapters": [{ "headline": "Intros", "gist": "Intros", "summary": "Intros", "start": 0, "end": 6500 }],
+        "utterances": [
+            { "speaker": "A", "text": "Glad to join today.", "start": 200, "end": 3500, "words": [
+                { "text": "Glad", "start": 250, "end": 600, "confidence": 0.95 },
+                { "text": "to", "start": 600, "end": 800, "confidence": 0.95 },
+                { "text": "join", "start": 800, "end": 1200, "confidence": 0.95 },
+                { "text": "today.", "start": 1200, "end": 3500, "confidence": 0.95 }
+            ]},
+            { "speaker": "B", "text": "Let's begin.", "start": 4000, "end": 5000, "words": [
+                { "text": "Let's", "start": 4000, "end": 4500, "confidence": 0.9 },
+                { "text": "begin.", "start": 4500, "end": 5000, "confidence": 0.9 }
+            ]},
+            { "speaker": "C", "text": "Hi.", "start": 6000, "end": 6500, "words": [
+                { "text": "Hi.", "start": 6000, "end": 6500, "confidence": 0.9 }
+            ]}
+        ]
+    }));
+
+    // Order of the input doesn't matter
+    let merged = merge_chunk_transcripts(vec![second, first]);
+    let utterances = merged.utterances.unwrap();
+    let turns: Vec<(&str, &str, i64, i64)> = utterances
+        .iter()
+        .map(|u| (u.speaker.as_str(), u.text.as_str(), u.start, u.end))
+        .collect();
+    assert_eq!(
+        turns,
+        vec![
+            ("A", "Welcome everyone.", 1000, 3000),
+            // The first chunk's copy of the overlap is dropped...
+            ("B", "Thanks.", 9000, 9500),
+            // ...the second chunk's A said the same words, so is the first chunk's B
+            ("B", "Glad to join today.", 10200, 13500),
+            // Speakers who didn't talk in the overlap can't be matched, so get new labels
+            ("C", "Let's begin.", 14000, 15000),
+            ("D", "Hi.", 16000, 16500),
+        ]
+    );
+    assert_eq!(utterances[2].words[0].start, 10250);
+
+    assert_eq!(merged.chapters.unwrap()[0].start, 10_000);
+
+    assert_eq!(merged.id, "t1");
+    assert_eq!(merged.extra["chunk_transcript_ids"], json!(["t1", "t2"]));
+    assert_eq!(merged.summary.as_deref(), Some("- Opening remarks\n- Introductions"));
+    assert_eq!(
+        merged.text.as_deref(),
+        Some("Welcome everyone. Thanks. Glad to join today. Let's begin. Hi.")
+    );
+}
+
+#[tokio::test]
+async fn cancelling_a_parallel_job_stops_uploads_in_flight() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server);
+    server.delay_next("POST", "/v2/upload", Duration::from_secs(10), 3);
+    let dir = tempfile::tempdir().unwrap();
+
+    let cancel = CancellationToken::new();
+    let canceller = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        canceller.cancel();
+    });
+    let started = Instant::now();
+    let err = upload_all(&provider, write_chunks(&dir, 3), &cancel).await.unwrap_err();
+
+    assert!(matches!(err, TranscribeError::Cancelled), "unexpected error: {}", err);
+    assert!(started.elapsed() < Duration::from_secs(2));
+    // The third chunk was still waiting for a slot and never started
+    assert_eq!(server.requests_to("POST", "/v2/upload").len(), 2);
+}
+
+#[tokio::test]
+async fn the_first_failed_chunk_stops_the_rest() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server);
+    server.delay_next("POST", "/v2/upload", Duration::from_secs(10), 4);
+    let dir = tempfile::tempdir().unwrap();
+    let mut paths = write_chunks(&dir, 4);
+    paths[1] = dir.path().join("missing.m4a");
+
+    let started = Instant::now();
+    let err = upload_all(&provider, paths, &CancellationToken::new()).await.unwrap_err();
+
+    assert!(matches!(err, TranscribeError::FileError(_)), "unexpected error: {}", err);
+    assert!(started.elapsed() < Duration::from_secs(2));
+    // Only the first chunk may have started uploading; the last two never did
+    assert!(server.requests_to("POST", "/v2/upload").len() <= 1);
+}
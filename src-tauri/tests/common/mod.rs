@@ -0,0 +1,384 @@
+// src-tauri/tests/common/mod.rs
+// Minimal in-process stand-in for the AssemblyAI and OpenAI HTTP APIs,
+// so the integration tests can run the full flows offline.
+//
+// Point the app at it with OHG_ASSEMBLYAI_BASE_URL / OHG_OPENAI_BASE_URL,
+// or pass `base_url()` straight to the provider / request functions.
+
+#![allow(dead_code)]
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// API key the mock accepts; anything else gets a 401
+pub const MOCK_API_KEY: &str = "test-key";
+
+/// A request the mock received
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    /// Host the request was addressed to when it arrived in proxy (absolute URL) form
+    pub proxied_host: Option<String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+}
+
+// A canned failure returned instead of the normal response
+struct InjectedFailure {
+    method: String,
+    path: String,
+    status: &'static str,
+    retry_after: Option<String>,
+    remaining: u32,
+}
+
+// A pause before answering, long enough for the client to time out
+struct InjectedDelay {
+    method: String,
+    path: String,
+    delay: Duration,
+    remaining: u32,
+}
+
+#[derive(Default)]
+struct MockState {
+    requests: Vec<RecordedRequest>,
+    failures: Vec<InjectedFailure>,
+    delays: Vec<InjectedDelay>,
+    // transcript id -> request body it was submitted with
+    transcripts: HashMap<String, Value>,
+    // transcript id -> number of times it has been polled
+    polls: HashMap<String, u32>,
+}
+
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockServer {
+    /// Bind to a random local port and start serving in the background
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind mock server");
+        let addr = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(MockState::default()));
+
+        let server_state = state.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = server_state.clone();
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, addr, state).await;
+                });
+            }
+        });
+
+        Self { addr, state }
+    }
+
+    /// Root URL, e.g. http://127.0.0.1:54321 (use as either API's base URL)
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Answer the next `times` requests to `method path` with `status`
+    /// (e.g. "503 Service Unavailable"), optionally sending a Retry-After header
+    pub fn fail_next(&self, method: &str, path: &str, status: &'static str, retry_after: Option<&str>, times: u32) {
+        self.state.lock().unwrap().failures.push(InjectedFailure {
+            method: method.to_string(),
+            path: path.to_string(),
+            status,
+            retry_after: retry_after.map(str::to_string),
+            remaining: times,
+        });
+    }
+
+    /// Hold the response to the next `times` requests to `method path` for `delay`
+    pub fn delay_next(&self, method: &str, path: &str, delay: Duration, times: u32) {
+        self.state.lock().unwrap().delays.push(InjectedDelay {
+            method: method.to_string(),
+            path: path.to_string(),
+            delay,
+            remaining: times,
+        });
+    }
+
+    /// Every request received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Requests received for a method and path
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.method == method && r.path == path)
+            .collect()
+    }
+}
+
+// Serve a single request, then close the connection
+async fn handle_connection(
+    stream: TcpStream,
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    
+    // Requests sent through a proxy carry the full URL: split off the host
+    let (proxied_host, path) = match target.strip_prefix("http://") {
+        Some(rest) => {
+            let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            (Some(host.to_string()), path.to_string())
+        }
+        None => (None, target.to_string()),
+    };
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let body = if headers.get("transfer-encoding").map(|v| v.contains("chunked")).unwrap_or(false) {
+        read_chunked(&mut reader).await?
+    } else {
+        let length: usize = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+        let mut body = vec![0u8; length];
+        reader.read_exact(&mut body).await?;
+        body
+    };
+
+    let request = RecordedRequest { method, path, proxied_host, headers, body };
+    let (status, response, retry_after) = match take_failure(&request, &state) {
+        Some((status, retry_after)) => (status, json!({ "error": "Injected failure" }), retry_after),
+        None => {
+            let (status, response) = route(&request, addr, &state);
+            (status, response, None)
+        }
+    };
+    let delay = take_delay(&request, &state);
+    state.lock().unwrap().requests.push(request);
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let body = response.to_string();
+    let retry_after = retry_after.map(|v| format!("Retry-After: {}\r\n", v)).unwrap_or_default();
+    let reply = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        retry_after,
+        body
+    );
+    let mut stream = reader.into_inner();
+    stream.write_all(reply.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn take_failure(request: &RecordedRequest, state: &Mutex<MockState>) -> Option<(&'static str, Option<String>)> {
+    let mut state = state.lock().unwrap();
+    let failure = state
+        .failures
+        .iter_mut()
+        .find(|f| f.remaining > 0 && f.method == request.method && f.path == request.path)?;
+    failure.remaining -= 1;
+    Some((failure.status, failure.retry_after.clone()))
+}
+
+fn take_delay(request: &RecordedRequest, state: &Mutex<MockState>) -> Option<Duration> {
+    let mut state = state.lock().unwrap();
+    let delay = state
+        .delays
+        .iter_mut()
+        .find(|d| d.remaining > 0 && d.method == request.method && d.path == request.path)?;
+    delay.remaining -= 1;
+    Some(delay.delay)
+}
+
+async fn read_chunked(reader: &mut BufReader<TcpStream>) -> std::io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    loop {
+        let mut size_line = String::new();
+        reader.read_line(&mut size_line).await?;
+        let size = usize::from_str_radix(size_line.trim().split(';').next().unwrap_or("0"), 16).unwrap_or(0);
+        let mut chunk = vec![0u8; size + 2]; // chunk data + CRLF
+        reader.read_exact(&mut chunk).await?;
+        if size == 0 {
+            return Ok(body);
+        }
+        body.extend_from_slice(&chunk[..size]);
+    }
+}
+
+fn route(request: &RecordedRequest, addr: SocketAddr, state: &Mutex<MockState>) -> (&'static str, Value) {
+    let auth = request.headers.get("authorization").map(String::as_str).unwrap_or_default();
+    if auth != MOCK_API_KEY && auth != format!("Bearer {}", MOCK_API_KEY) {
+        return ("401 Unauthorized", json!({ "error": "Authentication error, API token missing/invalid" }));
+    }
+
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["v2", "upload"]) => {
+            let n = state.lock().unwrap().requests.len();
+            ("200 OK", json!({ "upload_url": format!("http://{}/files/upload-{}", addr, n) }))
+        }
+        ("POST", ["v2", "transcript"]) => {
+            let mut state = state.lock().unwrap();
+            let id = format!("transcript-{}", state.transcripts.len() + 1);
+            state.transcripts.insert(id.clone(), request.json());
+            ("200 OK", json!({ "id": id, "status": "queued" }))
+        }
+        ("GET", ["v2", "transcript", id]) => {
+            let mut state = state.lock().unwrap();
+            let Some(submitted) = state.transcripts.get(*id).cloned() else {
+                return ("404 Not Found", json!({ "error": "Transcript not found" }));
+            };
+            let polls = state.polls.entry(id.to_string()).or_insert(0);
+            *polls += 1;
+            if *polls == 1 {
+                ("200 OK", json!({ "id": id, "status": "processing" }))
+            } else if submitted["audio_url"].as_str().unwrap_or_default().contains("fail") {
+                ("200 OK", json!({ "id": id, "status": "error", "error": "Audio file could not be decoded" }))
+            } else {
+                ("200 OK", completed_transcript(id, &submitted))
+            }
+        }
+        ("DELETE", ["v2", "transcript", id]) => {
+            let mut state = state.lock().unwrap();
+            if state.transcripts.remove(*id).is_none() {
+                return ("404 Not Found", json!({ "error": "Transcript not found" }));
+            }
+            ("200 OK", json!({ "id": id, "status": "completed", "text": "Deleted by user." }))
+        }
+        ("POST", ["lemur", "v3", "generate", "question-answer"]) => {
+            let answers: Vec<Value> = request.json()["questions"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|q| {
+                    let question = q["question"].as_str().unwrap_or_default().to_string();
+                    let answer = if question.ends_with(" A?") { "Alice Smith" } else { "Unknown" };
+                    json!({ "question": question, "answer": answer })
+                })
+                .collect();
+            ("200 OK", json!({ "request_id": "lemur-1", "response": answers }))
+        }
+        ("POST", ["v1", "chat", "completions"]) => {
+            let content = json!({
+                "categories": [
+                    { "name": "Drug Names", "terms": ["semaglutide", "Ozempic"] },
+                    { "name": "Acronyms", "terms": ["GLP-1", "HbA1c"] }
+                ],
+                "suggested_name": "Semaglutide Advisory Board"
+            });
+            ("200 OK", json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": content.to_string() },
+                    "finish_reason": "stop"
+                }]
+            }))
+        }
+        _ => ("404 Not Found", json!({ "error": format!("No mock for {} {}", request.method, request.path) })),
+    }
+}
+
+// A finished transcript, with results for whichever optional features were requested
+fn completed_transcript(id: &str, submitted: &Value) -> Value {
+    let mut transcript = json!({
+        "id": id,
+        "status": "completed",
+        "text": "Welcome to the advisory board. Thanks, happy to be here.",
+        "utterances": [
+            {
+                "speaker": "A",
+                "text": "Welcome to the advisory board.",
+                "start": 0,
+                "end": 2400,
+                "confidence": 0.97,
+                "words": [
+                    { "text": "Welcome", "start": 0, "end": 500, "confidence": 0.99, "speaker": "A" },
+                    { "text": "to", "start": 500, "end": 650, "confidence": 0.98, "speaker": "A" },
+                    { "text": "the", "start": 650, "end": 800, "confidence": 0.98, "speaker": "A" },
+                    { "text": "advisory", "start": 800, "end": 1600, "confidence": 0.91, "speaker": "A" },
+                    { "text": "board.", "start": 1600, "end": 2400, "confidence": 0.96, "speaker": "A" }
+                ]
+            },
+            {
+                "speaker": "B",
+                "text": "Thanks, happy to be here.",
+                "start": 2600,
+                "end": 4200,
+                "confidence": 0.88,
+                "words": [
+                    { "text": "Thanks,", "start": 2600, "end": 3000, "confidence": 0.93, "speaker": "B" },
+                    { "text": "happy", "start": 3000, "end": 3400, "confidence": 0.52, "speaker": "B" },
+                    { "text": "to", "start": 3400, "end": 3550, "confidence": 0.97, "speaker": "B" },
+                    { "text": "be", "start": 3550, "end": 3700, "confidence": 0.97, "speaker": "B" },
+                    { "text": "here.", "start": 3700, "end": 4200, "confidence": 0.9, "speaker": "B" }
+                ]
+            }
+        ],
+        "audio_duration": 5
+    });
+
+    if submitted["language_detection"] == json!(true) {
+        transcript["language_code"] = json!("en");
+        transcript["language_confidence"] = json!(0.97);
+    } else {
+        transcript["language_code"] = submitted.get("language_code").cloned().unwrap_or(json!("en_us"));
+    }
+    if submitted["auto_chapters"] == json!(true) {
+        transcript["chapters"] = json!([{
+            "headline": "Welcome to the advisory board",
+            "gist": "Introductions",
+            "summary": "The host welcomes a guest to the advisory board.",
+            "start": 0,
+            "end": 4200
+        }]);
+    }
+    // Like the real API, rewrite misheard phrases in the text
+    for rule in submitted["custom_spelling"].as_array().cloned().unwrap_or_default() {
+        let to = rule["to"].as_str().unwrap_or_default();
+        for from in rule["from"].as_array().cloned().unwrap_or_default() {
+            let text = transcript["text"].as_str().unwrap_or_default().replace(from.as_str().unwrap_or_default(), to);
+            transcript["text"] = json!(text);
+        }
+    }
+    if submitted["entity_detection"] == json!(true) {
+        transcript["entities"] = json!([
+            { "entity_type": "organization", "text": "advisory board", "start": 650, "end": 2400 }
+        ]);
+    }
+    transcript
+}
@@ -0,0 +1,50 @@
+// Reading FFmpeg's `-progress` output into percent complete and ETA
+
+use std::time::Duration;
+
+use ohg_scribe_lib::commands::convert::ProgressTracker;
+
+const LOG: &str = "\
+Input #0, mov,mp4,m4a,3gp,3g2,mj2, from 'meeting.mp4':
+  Duration: 00:10:00.00, start: 0.000000, bitrate: 1200 kb/s
+Output #0, ipod, to 'meeting.m4a':
+";
+
+fn read_block(tracker: &mut ProgressTracker, block: &str) -> usize {
+    block.lines().filter(|line| tracker.read_progress_line(line)).count()
+}
+
+#[test]
+fn progress_is_measured_against_the_input_duration() {
+    let mut tracker = ProgressTracker::default();
+
+    // Progress before the log names a duration can't be turned into a percentage
+    read_block(&mut tracker, "out_time_us=N/A\nspeed=N/A\nprogress=continue\n");
+    assert_eq!(tracker.percent(), None);
+
+    LOG.lines().for_each(|line| tracker.read_log_line(line));
+    let ended = read_block(
+        &mut tracker,
+        "frame=0\nout_time_us=150000000\nout_time=00:02:30.000000\nspeed=30x\nprogress=continue\n",
+    );
+    assert_eq!(ended, 1);
+    assert_eq!(tracker.percent(), Some(25.0));
+
+    // A quarter done in 10s leaves 30s at the same rate
+    let progress = tracker.snapshot("job-1", Duration::from_secs(10));
+    assert_eq!(progress.job_id, "job-1");
+    assert_eq!(progress.processed_ms, 150_000);
+    assert_eq!(progress.duration_ms, Some(600_000));
+    assert_eq!(progress.eta_secs, Some(30));
+}
+
+#[test]
+fn end_of_run_reports_complete() {
+    let mut tracker = ProgressTracker::default();
+    LOG.lines().for_each(|line| tracker.read_log_line(line));
+
+    // The last block can stop short of the container duration
+    read_block(&mut tracker, "out_time_us=599800000\nprogress=end\n");
+    assert_eq!(tracker.percent(), Some(100.0));
+    assert_eq!(tracker.eta(Duration::from_secs(40)), Some(Duration::ZERO));
+}
@@ -0,0 +1,89 @@
+// Shared HTTP client configuration: proxy routing and extra root certificates
+
+mod common;
+
+use common::{MockServer, MOCK_API_KEY};
+use ohg_scribe_lib::commands::settings::NetworkSettings;
+use ohg_scribe_lib::commands::transcribe::TranscriptionOptions;
+use ohg_scribe_lib::http::{build_client, HttpError, RetryPolicy};
+use ohg_scribe_lib::providers::{AssemblyAiProvider, TranscriptionProvider};
+
+// Self-signed root used to check that PEM bundles load
+const TEST_CA: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/test-ca.pem");
+
+fn options() -> TranscriptionOptions {
+    serde_json::from_value(serde_json::json!({
+        "boost_words": [],
+        "include_summary": false,
+        "detect_topics": false,
+        "analyze_sentiment": false,
+        "extract_key_phrases": false,
+        "speaker_label_mode": "generic",
+        "speaker_values": []
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn requests_go_through_the_configured_proxy() {
+    let proxy = MockServer::start().await;
+    let network = NetworkSettings {
+        proxy_url: Some(proxy.base_url()),
+        proxy_username: Some("corp-user".to_string()),
+        proxy_password: Some("s3cret".to_string()),
+        ..NetworkSettings::default()
+    };
+    let client = build_client(&network).unwrap();
+
+    // The API host doesn't resolve, so the request only succeeds via the proxy
+    let provider = AssemblyAiProvider::new(client, "http://assemblyai.invalid/v2", MOCK_API_KEY)
+        .with_retry_policy(RetryPolicy::none());
+    let transcript_id = provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap();
+    assert_eq!(transcript_id, "transcript-1");
+
+    let submits = proxy.requests_to("POST", "/v2/transcript");
+    assert_eq!(submits.len(), 1);
+    assert_eq!(submits[0].proxied_host.as_deref(), Some("assemblyai.invalid"));
+    assert_eq!(
+        submits[0].headers.get("proxy-authorization").map(String::as_str),
+        Some("Basic Y29ycC11c2VyOnMzY3JldA==")
+    );
+}
+
+#[test]
+fn extra_root_certificates_are_loaded() {
+    let network = NetworkSettings {
+        ca_certificates: vec![TEST_CA.to_string()],
+        connect_timeout_secs: Some(5),
+        read_timeout_secs: Some(60),
+        ..NetworkSettings::default()
+    };
+    build_client(&network).unwrap();
+}
+
+#[test]
+fn unusable_network_settings_are_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let not_pem = dir.path().join("not-a-cert.pem");
+    std::fs::write(&not_pem, "hello").unwrap();
+
+    let cases = [
+        NetworkSettings {
+            ca_certificates: vec![dir.path().join("missing.pem").to_string_lossy().into_owned()],
+            ..NetworkSettings::default()
+        },
+        NetworkSettings {
+            ca_certificates: vec![not_pem.to_string_lossy().into_owned()],
+            ..NetworkSettings::default()
+        },
+        NetworkSettings {
+            proxy_url: Some("not a url".to_string()),
+            ..NetworkSettings::default()
+        },
+    ];
+
+    for network in cases {
+        let err = build_client(&network).unwrap_err();
+        assert!(matches!(err, HttpError::InvalidConfig(_)), "unexpected error: {:?}", err);
+    }
+}
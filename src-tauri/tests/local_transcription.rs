@@ -0,0 +1,52 @@
+// Mapping whisper.cpp output onto the transcript structures history and export use
+
+use ohg_scribe_lib::commands::local_transcribe::parse_whisper_output;
+use ohg_scribe_lib::commands::review::find_low_confidence_spans;
+use ohg_scribe_lib::providers::TranscriptStatus;
+
+fn fixture() -> String {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/whisper-full.json");
+    std::fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn whisper_segments_become_utterances_split_at_speaker_turns() {
+    let response = parse_whisper_output("local-1", &fixture()).unwrap();
+    assert_eq!(response.id, "local-1");
+    assert_eq!(response.status, TranscriptStatus::Completed);
+    assert_eq!(response.language_code.as_deref(), Some("en"));
+    assert_eq!(
+        response.text.as_deref(),
+        Some("Welcome to the advisory board. Thanks, happy to be here.")
+    );
+
+    // The turn after the first segment starts speaker B; the next two segments merge
+    let utterances = response.utterances.unwrap();
+    assert_eq!(utterances.len(), 2);
+    assert_eq!(utterances[0].speaker, "A");
+    assert_eq!((utterances[1].speaker.as_str(), utterances[1].start, utterances[1].end), ("B", 2600, 4200));
+    assert_eq!(utterances[1].text, "Thanks, happy to be here.");
+}
+
+#[test]
+fn tokens_join_into_words_with_confidence() {
+    let utterances = parse_whisper_output("local-1", &fixture()).unwrap().utterances.unwrap();
+
+    // Control tokens are dropped and sub-word tokens join the word before them
+    let words: Vec<&str> = utterances[0].words.iter().map(|w| w.text.as_str()).collect();
+    assert_eq!(words, vec!["Welcome", "to", "the", "advisory", "board."]);
+    let advisory = &utterances[0].words[3];
+    assert_eq!((advisory.start, advisory.end), (800, 1600));
+    assert!((advisory.confidence.unwrap() - 0.7).abs() < 1e-9);
+    assert_eq!(utterances[1].words.last().unwrap().text, "here.");
+
+    // Confidences feed the same review list as AssemblyAI transcripts
+    let spans = find_low_confidence_spans(&utterances, 0.6);
+    assert_eq!(spans.len(), 1);
+    assert_eq!((spans[0].speaker.as_str(), spans[0].text.as_str()), ("B", "happy"));
+}
+
+#[test]
+fn malformed_output_is_an_error() {
+    assert!(parse_whisper_output("local-1", "whisper_init_from_file: failed to load model").is_err());
+}
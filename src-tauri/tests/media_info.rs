@@ -0,0 +1,68 @@
+// Reading file details from the input description FFmpeg prints
+
+use ohg_scribe_lib::commands::media_info::{channel_count, parse_media_info, StreamKind};
+
+const PROBE: &str = "\
+Input #0, mov,mp4,m4a,3gp,3g2,mj2, from '/Users/me/Board Meeting.mp4':
+  Metadata:
+    major_brand     : isom
+    title           : Q3 Advisory Board
+  Duration: 01:02:03.50, start: 0.000000, bitrate: 1530 kb/s
+  Stream #0:0[0x1](und): Video: h264 (High) (avc1 / 0x31637661), yuv420p(tv, bt709, progressive), 1920x1080 [SAR 1:1 DAR 16:9], 1400 kb/s, 30 fps, 30 tbr, 15360 tbn (default)
+    Metadata:
+      handler_name    : VideoHandler
+  Stream #0:1[0x2](eng): Audio: aac (LC) (mp4a / 0x6134706D), 48000 Hz, stereo, fltp, 128 kb/s (default)
+    Metadata:
+      handler_name    : SoundHandler
+  Stream #0:2[0x3](spa): Audio: aac (LC) (mp4a / 0x6134706D), 44100 Hz, 5.1(side), fltp, 384 kb/s
+    Metadata:
+      title           : Interpreter
+  Stream #0:3[0x4](eng): Subtitle: mov_text (tx3g / 0x67337874), 0 kb/s
+At least one output file must be specified
+";
+
+#[test]
+fn probe_output_lists_duration_and_streams() {
+    let info = parse_media_info(PROBE).unwrap();
+    assert_eq!(info.container, "mov,mp4,m4a,3gp,3g2,mj2");
+    assert_eq!(info.duration_ms, Some(3_723_500));
+    assert_eq!(info.bitrate_kbps, Some(1530));
+
+    let kinds: Vec<StreamKind> = info.streams.iter().map(|s| s.kind).collect();
+    assert_eq!(kinds, [StreamKind::Video, StreamKind::Audio, StreamKind::Audio, StreamKind::Subtitle]);
+
+    let video = &info.streams[0];
+    assert_eq!((video.codec.as_str(), video.width, video.height), ("h264", Some(1920), Some(1080)));
+    assert_eq!(video.language, None);
+
+    // The container's title isn't mistaken for a stream's
+    let audio: Vec<_> = info.audio_streams().collect();
+    assert_eq!(audio.len(), 2);
+    assert_eq!((audio[0].index, audio[0].sample_rate, audio[0].channels), (1, Some(48000), Some(2)));
+    assert!(audio[0].default);
+    assert_eq!(audio[0].title, None);
+    assert_eq!(audio[1].language.as_deref(), Some("spa"));
+    assert_eq!(audio[1].channel_layout.as_deref(), Some("5.1(side)"));
+    assert_eq!(audio[1].channels, Some(6));
+    assert_eq!(audio[1].title.as_deref(), Some("Interpreter"));
+
+    assert_eq!(info.streams[3].codec, "mov_text");
+}
+
+#[test]
+fn files_without_audio_or_media_are_recognized() {
+    let silent = "\
+Input #0, image2, from 'slide.png':
+  Duration: 00:00:00.04, start: 0.000000, bitrate: N/A
+  Stream #0:0: Video: png, rgba(pc), 1280x720, 25 fps, 25 tbr, 25 tbn
+";
+    let info = parse_media_info(silent).unwrap();
+    assert!(!info.has_audio());
+    assert_eq!(info.bitrate_kbps, None);
+
+    assert_eq!(parse_media_info("notes.txt: Invalid data found when processing input"), None);
+
+    assert_eq!(channel_count("mono"), Some(1));
+    assert_eq!(channel_count("7.1(wide)"), Some(8));
+    assert_eq!(channel_count("4 channels"), Some(4));
+}
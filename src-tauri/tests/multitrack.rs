@@ -0,0 +1,85 @@
+// Transcribing one track per participant and interleaving the tracks
+
+use ohg_scribe_lib::commands::convert::mix_filter_args;
+use ohg_scribe_lib::commands::multitrack::{interleave_tracks, split_at_pauses, TrackTranscript, TRACK_PAUSE_MS};
+use ohg_scribe_lib::commands::timeline::{TimeRange, TimelineMap};
+use ohg_scribe_lib::providers::{TranscriptResponse, Utterance};
+
+fn track(name: &str, response: serde_json::Value) -> TrackTranscript {
+    TrackTranscript {
+        name: name.to_string(),
+        timeline: None,
+        response: serde_json::from_value(response).unwrap(),
+    }
+}
+
+#[test]
+fn long_pauses_within_a_track_split_utterances() {
+    let utterance: Utterance = serde_json::from_value(serde_json::json!({
+        "speaker": "A", "text": "Hi. So, about the budget", "start": 0, "end": 6_000,
+        "words": [
+            { "text": "Hi.", "start": 0, "end": 400, "confidence": 0.9 },
+            { "text": "So,", "start": 4_000, "end": 4_300, "confidence": 0.8 },
+            { "text": "about", "start": 4_400, "end": 4_800, "confidence": 0.6 },
+            { "text": "the", "start": 4_900, "end": 5_000, "confidence": 1.0 },
+            { "text": "budget", "start": 5_100, "end": 6_000, "confidence": 1.0 }
+        ]
+    }))
+    .unwrap();
+    let parts = split_at_pauses(utterance.clone(), TRACK_PAUSE_MS);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].text, "Hi.");
+    assert_eq!((parts[1].start, parts[1].end), (4_000, 6_000));
+    assert_eq!(parts[1].text, "So, about the budget");
+    assert_eq!(parts[1].confidence, Some(0.85));
+    
+    // No pause long enough: the utterance comes back as it was
+    let parts = split_at_pauses(utterance, 5_000);
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].text, "Hi. So, about the budget");
+}
+
+#[test]
+fn tracks_interleave_under_their_names() {
+    let alice = track("Alice", serde_json::json!({
+        "id": "t-alice",
+        "status": "completed",
+        "language_code": "en",
+        "utterances": [
+            { "speaker": "A", "text": "Shall we start?", "start": 1_000, "end": 2_000,
+              "words": [{ "text": "Shall", "start": 1_000, "end": 1_300 }] },
+            { "speaker": "A", "text": "Great.", "start": 5_000, "end": 5_500 }
+        ]
+    }));
+    let mut bob = track("Bob", serde_json::json!({
+        "id": "t-bob",
+        "status": "completed",
+        "utterances": [
+            { "speaker": "A", "text": "Yes, go ahead.", "start": 1_000, "end": 2_000 }
+        ]
+    }));
+    // Bob's track had its first minute of silence cut
+    bob.timeline = Some(TimelineMap::from_ranges(&[TimeRange { start_ms: 2_500, end_ms: 60_000 }]));
+    
+    let merged: TranscriptResponse = interleave_tracks(vec![alice, bob]);
+    let utterances = merged.utterances.as_ref().unwrap();
+    let order: Vec<(&str, i64)> = utterances.iter().map(|u| (u.speaker.as_str(), u.start)).collect();
+    assert_eq!(order, [("Alice", 1_000), ("Bob", 3_500), ("Alice", 5_000)]);
+    assert_eq!(utterances[0].words[0].speaker.as_deref(), Some("Alice"));
+    assert_eq!(merged.text.as_deref(), Some("Shall we start? Yes, go ahead. Great."));
+    assert_eq!(merged.language_code.as_deref(), Some("en"));
+    assert_eq!(merged.extra["track_transcript_ids"], serde_json::json!(["t-alice", "t-bob"]));
+}
+
+#[test]
+fn tracks_are_mixed_at_full_level() {
+    assert_eq!(
+        mix_filter_args(3),
+        [
+            "-filter_complex",
+            "[0:a:0][1:a:0][2:a:0]amix=inputs=3:duration=longest:normalize=0[mixed]",
+            "-map",
+            "[mixed]"
+        ]
+    );
+}
@@ -0,0 +1,52 @@
+// Joining a meeting recorded in several files into one transcript
+
+use ohg_scribe_lib::commands::convert::concat_filter_args;
+use ohg_scribe_lib::commands::timeline::{locate_source, locate_utterances, session_files};
+use ohg_scribe_lib::providers::{SourcePosition, TranscriptResponse};
+
+#[test]
+fn session_times_are_located_in_their_file() {
+    let paths = ["zoom_0.mp4".to_string(), "zoom_1.mp4".to_string(), "zoom_2.mp4".to_string()];
+    let files = session_files(&paths, &[1_800_000, 600_000, 1_200_000]);
+    assert_eq!(files[1].offset_ms, 1_800_000);
+    assert_eq!(files[2].offset_ms, 2_400_000);
+
+    assert_eq!(locate_source(&files, 60_000), Some(SourcePosition { file: 0, local_ms: 60_000 }));
+    // A boundary belongs to the file starting there
+    assert_eq!(locate_source(&files, 1_800_000), Some(SourcePosition { file: 1, local_ms: 0 }));
+    assert_eq!(locate_source(&files, 2_500_000), Some(SourcePosition { file: 2, local_ms: 100_000 }));
+    assert_eq!(locate_source(&[], 5_000), None);
+
+    let mut response: TranscriptResponse = serde_json::from_value(serde_json::json!({
+        "id": "t-1",
+        "status": "completed",
+        "utterances": [
+            { "speaker": "A", "text": "Let's start.", "start": 2_000, "end": 3_000 },
+            { "speaker": "B", "text": "Sorry, I dropped.", "start": 1_805_000, "end": 1_807_000 }
+        ]
+    }))
+    .unwrap();
+    locate_utterances(&files, &mut response);
+    let utterances = response.utterances.as_ref().unwrap();
+    assert_eq!(utterances[0].source, Some(SourcePosition { file: 0, local_ms: 2_000 }));
+    assert_eq!(utterances[1].source, Some(SourcePosition { file: 1, local_ms: 5_000 }));
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["utterances"][1]["source"], serde_json::json!({ "file": 1, "local_ms": 5_000 }));
+}
+
+#[test]
+fn inputs_are_matched_then_joined_then_filtered() {
+    let args = concat_filter_args(2, &["loudnorm".to_string()]);
+    assert_eq!(
+        args,
+        [
+            "-filter_complex",
+            "[0:a:0]aformat=sample_fmts=fltp:sample_rates=16000:channel_layouts=mono[p0];\
+             [1:a:0]aformat=sample_fmts=fltp:sample_rates=16000:channel_layouts=mono[p1];\
+             [p0][p1]concat=n=2:v=0:a=1,loudnorm[session]",
+            "-map",
+            "[session]"
+        ]
+    );
+}
@@ -0,0 +1,61 @@
+// Cutting long silences out of a recording and mapping the transcript back
+
+use ohg_scribe_lib::commands::convert::{strip_silences, Silence, SilenceOptions};
+use ohg_scribe_lib::commands::timeline::{TimeRange, TimelineMap};
+use ohg_scribe_lib::providers::TranscriptResponse;
+
+fn range(start_ms: i64, end_ms: i64) -> TimeRange {
+    TimeRange { start_ms, end_ms }
+}
+
+fn silence(start_ms: i64, end_ms: i64) -> Silence {
+    Silence { start_ms, end_ms }
+}
+
+#[test]
+fn long_silences_are_cut_leaving_padding() {
+    let options = SilenceOptions::default();
+    // A 15 minute coffee break and a 5 s pause in a one hour recording
+    let silences = [silence(1_200_000, 2_100_000), silence(3_000_000, 3_005_000)];
+    let kept = strip_silences(&[], 3_600_000, &silences, &options);
+    assert_eq!(kept, [range(0, 1_201_000), range(2_099_000, 3_600_000)]);
+
+    // Nothing long enough: the whole file, no ranges
+    assert_eq!(strip_silences(&[], 3_600_000, &silences[1..], &options), []);
+}
+
+#[test]
+fn silences_are_cut_from_requested_ranges() {
+    let options = SilenceOptions { padding_secs: 0.0, ..SilenceOptions::default() };
+    let ranges = [range(0, 600_000), range(900_000, 1_500_000)];
+    // One silence straddles the end of the first range, one sits inside the second
+    let silences = [silence(500_000, 950_000), silence(1_000_000, 1_100_000)];
+    let kept = strip_silences(&ranges, 3_600_000, &silences, &options);
+    assert_eq!(kept, [range(0, 500_000), range(950_000, 1_000_000), range(1_100_000, 1_500_000)]);
+}
+
+#[test]
+fn transcript_after_a_break_lands_back_after_it() {
+    let kept = [range(0, 1_201_000), range(2_099_000, 3_600_000)];
+    let timeline = TimelineMap::from_ranges(&kept);
+
+    let mut response: TranscriptResponse = serde_json::from_value(serde_json::json!({
+        "id": "t-1",
+        "status": "completed",
+        "utterances": [
+            {
+                "speaker": "A", "text": "Welcome back.", "start": 1_202_000, "end": 1_203_500,
+                "words": [
+                    { "text": "Welcome", "start": 1_202_000, "end": 1_202_600 },
+                    { "text": "back.", "start": 1_202_700, "end": 1_203_500 }
+                ]
+            }
+        ]
+    }))
+    .unwrap();
+    timeline.remap_transcript(&mut response);
+
+    let utterance = &response.utterances.as_ref().unwrap()[0];
+    assert_eq!((utterance.start, utterance.end), (2_100_000, 2_101_500));
+    assert_eq!((utterance.words[1].start, utterance.words[1].end), (2_100_700, 2_101_500));
+}
@@ -0,0 +1,47 @@
+// Lifetime of the temp directories converted audio is written to
+
+use std::time::Duration;
+
+use ohg_scribe_lib::temp_workspace::TempWorkspaces;
+
+#[test]
+fn release_removes_managed_dirs_and_refuses_others() {
+    let root = tempfile::tempdir().unwrap();
+    let workspaces = TempWorkspaces::new(root.path().join("conversions"));
+
+    let dir = workspaces.create().unwrap();
+    std::fs::write(dir.join("audio.m4a"), b"audio").unwrap();
+    assert!(workspaces.is_managed(&dir));
+
+    // Paths the registry didn't create are left alone, even inside its root
+    let outside = tempfile::tempdir().unwrap();
+    assert!(workspaces.release(outside.path()).is_err());
+    assert!(outside.path().exists());
+    assert!(workspaces.release(workspaces.root()).is_err());
+
+    workspaces.release(&dir).unwrap();
+    assert!(!dir.exists());
+    assert!(workspaces.release(&dir).is_err());
+}
+
+#[test]
+fn release_all_and_sweep_clear_leftovers() {
+    let root = tempfile::tempdir().unwrap();
+    let workspaces = TempWorkspaces::new(root.path().to_path_buf());
+    let live = workspaces.create().unwrap();
+
+    // A directory left behind by an earlier run
+    let leftover = root.path().join("job-crashed");
+    std::fs::create_dir(&leftover).unwrap();
+
+    assert_eq!(workspaces.sweep_stale(Duration::from_secs(3600)), 0);
+    assert!(leftover.exists());
+
+    // Tracked directories are never swept
+    assert_eq!(workspaces.sweep_stale(Duration::ZERO), 1);
+    assert!(!leftover.exists());
+    assert!(live.exists());
+
+    workspaces.release_all();
+    assert!(!live.exists());
+}
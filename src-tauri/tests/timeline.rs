@@ -0,0 +1,75 @@
+// Transcribing parts of a recording and mapping the transcript back onto it
+
+use ohg_scribe_lib::commands::convert::trim_args;
+use ohg_scribe_lib::commands::timeline::{normalize_ranges, TimeRange, TimelineMap};
+use ohg_scribe_lib::providers::TranscriptResponse;
+
+fn range(start_ms: i64, end_ms: i64) -> TimeRange {
+    TimeRange { start_ms, end_ms }
+}
+
+#[test]
+fn ranges_are_sorted_merged_and_checked() {
+    let ranges = normalize_ranges(&[range(60_000, 90_000), range(0, 10_000), range(5_000, 20_000)]).unwrap();
+    assert_eq!(ranges, [range(0, 20_000), range(60_000, 90_000)]);
+
+    assert!(normalize_ranges(&[range(10_000, 10_000)]).is_err());
+    assert!(normalize_ranges(&[range(-1, 10_000)]).is_err());
+    assert_eq!(normalize_ranges(&[]).unwrap(), []);
+}
+
+#[test]
+fn ffmpeg_reads_only_the_requested_ranges() {
+    // Minutes 12-47: seek in and stop, no filter needed
+    let (input, filter) = trim_args(&[range(720_000, 2_820_000)]);
+    assert_eq!(input, ["-ss", "720.000", "-t", "2100.000"]);
+    assert_eq!(filter, None);
+
+    let (input, filter) = trim_args(&[range(10_000, 20_000), range(30_000, 35_500)]);
+    assert_eq!(input, ["-ss", "10.000", "-t", "25.500"]);
+    assert_eq!(
+        filter.as_deref(),
+        Some("aselect='between(t,0.000,10.000)+between(t,20.000,25.500)',asetpts=N/SR/TB")
+    );
+}
+
+#[test]
+fn transcript_times_map_back_to_the_source() {
+    let timeline = TimelineMap::from_ranges(&[range(720_000, 730_000), range(900_000, 910_000)]);
+    assert!(!timeline.is_identity());
+    assert_eq!(timeline.to_source(2_000), 722_000);
+    // The boundary between the two kept parts: an end belongs to the first, a start to the second
+    assert_eq!(timeline.to_source_end(10_000), 730_000);
+    assert_eq!(timeline.to_source(10_000), 900_000);
+    assert_eq!(timeline.to_output(905_000), Some(15_000));
+    assert_eq!(timeline.to_output(800_000), None);
+
+    let mut response: TranscriptResponse = serde_json::from_value(serde_json::json!({
+        "id": "t-1",
+        "status": "completed",
+        "utterances": [
+            {
+                "speaker": "A", "text": "Item four.", "start": 1000, "end": 10000,
+                "words": [
+                    { "text": "Item", "start": 1000, "end": 1400 },
+                    { "text": "four.", "start": 1500, "end": 10000 }
+                ]
+            },
+            { "speaker": "B", "text": "Approved.", "start": 10000, "end": 11200, "words": [] }
+        ],
+        "chapters": [
+            { "headline": "h", "gist": "g", "summary": "s", "start": 1000, "end": 11200 }
+        ],
+        "words": [ { "text": "Approved.", "start": 10000, "end": 11200 } ]
+    }))
+    .unwrap();
+    timeline.remap_transcript(&mut response);
+
+    let utterances = response.utterances.as_ref().unwrap();
+    assert_eq!((utterances[0].start, utterances[0].end), (721_000, 730_000));
+    assert_eq!((utterances[0].words[1].start, utterances[0].words[1].end), (721_500, 730_000));
+    assert_eq!((utterances[1].start, utterances[1].end), (900_000, 901_200));
+    let chapter = &response.chapters.as_ref().unwrap()[0];
+    assert_eq!((chapter.start, chapter.end), (721_000, 901_200));
+    assert_eq!(response.extra["words"][0]["start"], 900_000);
+}
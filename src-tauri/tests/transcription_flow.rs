@@ -0,0 +1,452 @@
+// Full AssemblyAI transcription flow against the local mock server
+
+mod common;
+
+use common::{MockServer, MOCK_API_KEY};
+use ohg_scribe_lib::commands::review::find_low_confidence_spans;
+use ohg_scribe_lib::commands::settings::ApiEndpoints;
+use ohg_scribe_lib::commands::speaker_roles::{resolve_role_set, RoleSet, RoleSetError};
+use ohg_scribe_lib::commands::transcribe::{
+    wait_for_transcript, CustomSpelling, PollSchedule, SpeakerLabelMode, TranscribeError,
+    TranscriptionOptions,
+};
+use ohg_scribe_lib::http::RetryPolicy;
+use ohg_scribe_lib::providers::{AssemblyAiProvider, TranscriptStatus, TranscriptionProvider, Utterance};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+// Retry quickly so failure cases don't slow the suite down
+const FAST_RETRY: RetryPolicy = RetryPolicy::new(3, Duration::from_millis(10), Duration::from_millis(50));
+
+fn provider_for(server: &MockServer, api_key: &str) -> AssemblyAiProvider {
+    let endpoints = ApiEndpoints {
+        assemblyai: server.base_url(),
+        ..ApiEndpoints::default()
+    };
+    AssemblyAiProvider::new(reqwest::Client::new(), endpoints.assemblyai_v2(), api_key).with_retry_policy(FAST_RETRY)
+}
+
+// Poll often enough that a wait finishes within a few milliseconds
+const FAST_POLL: PollSchedule = PollSchedule {
+    min_interval: Duration::from_millis(10),
+    max_interval: Duration::from_millis(20),
+};
+
+fn options() -> TranscriptionOptions {
+    serde_json::from_value(serde_json::json!({
+        "max_speakers": 2,
+        "boost_words": ["semaglutide", "GLP-1"],
+        "include_summary": false,
+        "detect_topics": true,
+        "analyze_sentiment": false,
+        "extract_key_phrases": false,
+        "speaker_label_mode": "interview",
+        "speaker_values": []
+    }))
+    .unwrap()
+}
+
+fn spelling(from: &[&str], to: &str) -> CustomSpelling {
+    CustomSpelling {
+        from: from.iter().map(|f| f.to_string()).collect(),
+        to: to.to_string(),
+    }
+}
+
+fn write_audio(dir: &tempfile::TempDir, len: usize) -> std::path::PathBuf {
+    let path = dir.path().join("meeting.m4a");
+    let bytes: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn uploads_submits_and_polls_to_completion() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+
+    let dir = tempfile::tempdir().unwrap();
+    let audio_len = 1024 * 1024 + 17;
+    let audio = write_audio(&dir, audio_len);
+
+    // Upload streams the whole file and reports progress up to its full size
+    let reported = Arc::new(AtomicU64::new(0));
+    let progress = reported.clone();
+    let upload_url = provider
+        .upload(&audio, Arc::new(move |sent, _total| progress.store(sent, Ordering::SeqCst)))
+        .await
+        .unwrap();
+    assert_eq!(reported.load(Ordering::SeqCst), audio_len as u64);
+    let uploads = server.requests_to("POST", "/v2/upload");
+    assert_eq!(uploads.len(), 1);
+    assert_eq!(uploads[0].body.len(), audio_len);
+
+    // Submission carries the options through to the request body
+    let transcript_id = provider.submit(&upload_url, &options()).await.unwrap();
+    let submitted = server.requests_to("POST", "/v2/transcript")[0].json();
+    assert_eq!(submitted["audio_url"], upload_url.as_str());
+    assert_eq!(submitted["speakers_expected"], 2);
+    assert_eq!(submitted["word_boost"], serde_json::json!(["semaglutide", "GLP-1"]));
+    assert_eq!(submitted["iab_categories"], true);
+    assert_eq!(
+        submitted["speech_understanding"]["request"]["speaker_identification"]["known_values"],
+        serde_json::json!(["Interviewer", "Interviewee"])
+    );
+
+    // First poll is still processing, the next one is complete
+    let first = provider.poll(&transcript_id).await.unwrap();
+    assert_eq!(first.status, TranscriptStatus::Processing);
+    let done = provider.poll(&transcript_id).await.unwrap();
+    assert_eq!(done.status, TranscriptStatus::Completed);
+    let utterances = done.utterances.unwrap();
+    assert_eq!(utterances.len(), 2);
+    assert_eq!(utterances[1].speaker, "B");
+    assert_eq!(utterances[1].start, 2600);
+
+    provider.delete(&transcript_id).await.unwrap();
+    assert_eq!(server.requests_to("DELETE", &format!("/v2/transcript/{}", transcript_id)).len(), 1);
+}
+
+#[tokio::test]
+async fn errored_transcript_is_reported() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+
+    let transcript_id = provider.submit("http://example.invalid/fail.m4a", &options()).await.unwrap();
+    provider.poll(&transcript_id).await.unwrap();
+    let response = provider.poll(&transcript_id).await.unwrap();
+
+    assert_eq!(response.status, TranscriptStatus::Error);
+    assert_eq!(response.error.as_deref(), Some("Audio file could not be decoded"));
+}
+
+#[tokio::test]
+async fn invalid_api_key_is_an_api_error() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, "wrong-key");
+
+    let err = provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap_err();
+
+    assert!(matches!(err, TranscribeError::ApiError(_)), "unexpected error: {:?}", err);
+    assert!(err.to_string().contains("401"));
+}
+
+#[tokio::test]
+async fn upload_is_retried_after_server_errors() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    server.fail_next("POST", "/v2/upload", "502 Bad Gateway", None, 2);
+
+    let dir = tempfile::tempdir().unwrap();
+    let audio = write_audio(&dir, 300 * 1024);
+    provider.upload(&audio, Arc::new(|_, _| {})).await.unwrap();
+
+    // The file is streamed in full on every attempt
+    let uploads = server.requests_to("POST", "/v2/upload");
+    assert_eq!(uploads.len(), 3);
+    assert!(uploads.iter().all(|u| u.body.len() == 300 * 1024));
+}
+
+#[tokio::test]
+async fn retry_after_header_overrides_backoff() {
+    let server = MockServer::start().await;
+    // A long backoff that the server's Retry-After: 0 should cut short
+    let slow_backoff = RetryPolicy::new(2, Duration::from_secs(20), Duration::from_secs(20));
+    let provider = AssemblyAiProvider::new(reqwest::Client::new(), format!("{}/v2", server.base_url()), MOCK_API_KEY)
+        .with_retry_policy(slow_backoff);
+    server.fail_next("POST", "/v2/transcript", "429 Too Many Requests", Some("0"), 1);
+
+    let started = Instant::now();
+    provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(server.requests_to("POST", "/v2/transcript").len(), 2);
+}
+
+#[tokio::test]
+async fn non_idempotent_requests_are_not_repeated_after_timeouts() {
+    let server = MockServer::start().await;
+    let provider = AssemblyAiProvider::new(reqwest::Client::new(), format!("{}/v2", server.base_url()), MOCK_API_KEY)
+        .with_retry_policy(FAST_RETRY.non_idempotent());
+
+    // The first attempt may have been accepted, so a second could bill a duplicate
+    server.fail_next("POST", "/v2/transcript", "408 Request Timeout", None, 1);
+    provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap_err();
+    assert_eq!(server.requests_to("POST", "/v2/transcript").len(), 1);
+
+    // Server errors and rate limits mean it wasn't, so those are still retried
+    server.fail_next("POST", "/v2/transcript", "503 Service Unavailable", None, 1);
+    provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap();
+    assert_eq!(server.requests_to("POST", "/v2/transcript").len(), 3);
+}
+
+#[tokio::test]
+async fn exhausted_retries_report_attempt_count() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    server.fail_next("POST", "/v2/transcript", "500 Internal Server Error", None, 10);
+
+    let err = provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap_err();
+
+    assert!(matches!(err, TranscribeError::ApiError(_)), "unexpected error: {:?}", err);
+    assert!(err.to_string().contains("after 3 attempt(s)"), "unexpected error: {}", err);
+}
+
+#[tokio::test]
+async fn wait_reports_each_status_change_until_completed() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let transcript_id = provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap();
+
+    let mut statuses = Vec::new();
+    let response = wait_for_transcript(
+        &provider,
+        &transcript_id,
+        &FAST_POLL,
+        Duration::from_secs(5),
+        &CancellationToken::new(),
+        |status| statuses.push(status),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.status, TranscriptStatus::Completed);
+    assert_eq!(statuses, vec![TranscriptStatus::Processing, TranscriptStatus::Completed]);
+}
+
+#[tokio::test]
+async fn wait_fails_on_errored_transcript() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let transcript_id = provider.submit("http://example.invalid/fail.m4a", &options()).await.unwrap();
+
+    let err = wait_for_transcript(&provider, &transcript_id, &FAST_POLL, Duration::from_secs(5), &CancellationToken::new(), |_| {})
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TranscribeError::TranscriptionFailed(ref msg) if msg == "Audio file could not be decoded"));
+}
+
+#[tokio::test]
+async fn wait_gives_up_at_the_deadline() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let transcript_id = provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap();
+
+    // The next poll would complete, but it isn't due until after the deadline
+    let slow_poll = PollSchedule {
+        min_interval: Duration::from_secs(30),
+        max_interval: Duration::from_secs(30),
+    };
+    let started = Instant::now();
+    let err = wait_for_transcript(&provider, &transcript_id, &slow_poll, Duration::from_millis(200), &CancellationToken::new(), |_| {})
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TranscribeError::Timeout), "unexpected error: {:?}", err);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn wait_stops_when_cancelled() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let transcript_id = provider.submit("http://example.invalid/audio.m4a", &options()).await.unwrap();
+
+    let slow_poll = PollSchedule {
+        min_interval: Duration::from_secs(30),
+        max_interval: Duration::from_secs(30),
+    };
+    let cancel = CancellationToken::new();
+    let canceller = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        canceller.cancel();
+    });
+
+    let err = wait_for_transcript(&provider, &transcript_id, &slow_poll, Duration::from_secs(60), &cancel, |_| {})
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TranscribeError::Cancelled), "unexpected error: {:?}", err);
+    assert_eq!(server.requests_to("GET", &format!("/v2/transcript/{}", transcript_id)).len(), 1);
+}
+
+#[tokio::test]
+async fn extended_options_are_sent_and_results_are_typed() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let mut options = options();
+    options.language_detection = true;
+    options.redact_pii = true;
+    options.redact_pii_policies = vec!["person_name".to_string(), "phone_number".to_string()];
+    options.auto_chapters = true;
+    options.entity_detection = true;
+    options.filter_profanity = true;
+    options.disfluencies = true;
+    options.boost_param = Some("high".to_string());
+
+    let transcript_id = provider.submit("http://example.invalid/audio.m4a", &options).await.unwrap();
+
+    let submitted = server.requests_to("POST", "/v2/transcript")[0].json();
+    assert_eq!(submitted["language_detection"], true);
+    assert!(submitted.get("language_code").is_none());
+    assert_eq!(submitted["redact_pii"], true);
+    assert_eq!(submitted["redact_pii_policies"], serde_json::json!(["person_name", "phone_number"]));
+    assert_eq!(submitted["auto_chapters"], true);
+    assert_eq!(submitted["entity_detection"], true);
+    assert_eq!(submitted["filter_profanity"], true);
+    assert_eq!(submitted["disfluencies"], true);
+    assert_eq!(submitted["boost_param"], "high");
+
+    provider.poll(&transcript_id).await.unwrap();
+    let response = provider.poll(&transcript_id).await.unwrap();
+
+    assert_eq!(response.language_code.as_deref(), Some("en"));
+    assert_eq!(response.language_confidence, Some(0.97));
+    let chapters = response.chapters.expect("chapters");
+    assert_eq!(chapters[0].gist, "Introductions");
+    let entities = response.entities.expect("entities");
+    assert_eq!(entities[0].entity_type, "organization");
+    for typed in ["language_code", "language_confidence", "chapters", "entities"] {
+        assert!(!response.extra.contains_key(typed), "{} left in extra", typed);
+    }
+}
+
+#[tokio::test]
+async fn unsupported_option_combinations_are_rejected_before_submitting() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+
+    let mut no_policies = options();
+    no_policies.redact_pii = true;
+    let mut chapters_and_summary = options();
+    chapters_and_summary.auto_chapters = true;
+    chapters_and_summary.include_summary = true;
+    let mut language_and_detection = options();
+    language_and_detection.language_code = Some("es".to_string());
+    language_and_detection.language_detection = true;
+    let mut bad_boost = options();
+    bad_boost.boost_param = Some("maximum".to_string());
+    let mut multi_word_spelling = options();
+    multi_word_spelling.custom_spelling = vec![spelling(&["sema glue tide"], "sema glutide")];
+    let mut conflicting_spellings = options();
+    conflicting_spellings.custom_spelling = vec![
+        spelling(&["oh zempic"], "Ozempic"),
+        spelling(&["Oh Zempic"], "Wegovy"),
+    ];
+
+    for options in [
+        no_policies,
+        chapters_and_summary,
+        language_and_detection,
+        bad_boost,
+        multi_word_spelling,
+        conflicting_spellings,
+    ] {
+        let err = provider.submit("http://example.invalid/audio.m4a", &options).await.unwrap_err();
+        assert!(matches!(err, TranscribeError::InvalidOptions(_)), "unexpected error: {:?}", err);
+    }
+    assert!(server.requests_to("POST", "/v2/transcript").is_empty());
+}
+
+#[tokio::test]
+async fn custom_spelling_corrects_misheard_terms() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let mut options = options();
+    options.custom_spelling = vec![spelling(&["advisory board", "advisery bored"], "AdBoard")];
+
+    let transcript_id = provider.submit("http://example.invalid/audio.m4a", &options).await.unwrap();
+
+    let submitted = server.requests_to("POST", "/v2/transcript")[0].json();
+    assert_eq!(
+        submitted["custom_spelling"],
+        serde_json::json!([{ "from": ["advisory board", "advisery bored"], "to": "AdBoard" }])
+    );
+
+    provider.poll(&transcript_id).await.unwrap();
+    let response = provider.poll(&transcript_id).await.unwrap();
+    assert!(response.text.unwrap().starts_with("Welcome to the AdBoard."));
+}
+
+#[tokio::test]
+async fn low_confidence_words_are_listed_for_review() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+
+    let transcript_id = provider.submit("https://cdn.example/audio", &options()).await.unwrap();
+    provider.poll(&transcript_id).await.unwrap();
+    let done = provider.poll(&transcript_id).await.unwrap();
+    let utterances = done.utterances.unwrap();
+    assert_eq!(utterances[1].confidence, Some(0.88));
+    assert_eq!(utterances[1].words[1].confidence, Some(0.52));
+
+    // Only the mumbled "happy" falls below 0.6
+    let spans = find_low_confidence_spans(&utterances, 0.6);
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].speaker, "B");
+    assert_eq!((spans[0].start, spans[0].end), (3000, 3400));
+    assert_eq!(spans[0].text, "happy");
+    assert_eq!(spans[0].confidence, 0.52);
+
+    // Neighbouring low words join into one span; spans never cross speaker turns
+    let spans = find_low_confidence_spans(&utterances, 0.95);
+    let texts: Vec<(&str, &str)> = spans.iter().map(|s| (s.speaker.as_str(), s.text.as_str())).collect();
+    assert_eq!(texts, vec![("A", "advisory"), ("B", "Thanks, happy"), ("B", "here.")]);
+    assert_eq!((spans[1].start, spans[1].end, spans[1].word_count), (2600, 3400, 2));
+
+    // Segments saved without word timings are judged as a whole
+    let segments: Vec<Utterance> = serde_json::from_value(serde_json::json!([
+        { "speaker": "Interviewer", "text": "Welcome back.", "start": 0, "end": 900, "confidence": 0.55 },
+        { "speaker": "Interviewee", "text": "Thank you.", "start": 1000, "end": 1600, "confidence": 0.95 }
+    ]))
+    .unwrap();
+    let spans = find_low_confidence_spans(&segments, 0.6);
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].speaker, "Interviewer");
+    assert_eq!(spans[0].word_count, 2);
+}
+
+#[tokio::test]
+async fn saved_role_set_is_sent_as_known_roles() {
+    let server = MockServer::start().await;
+    let provider = provider_for(&server, MOCK_API_KEY);
+    let role_sets = vec![RoleSet {
+        id: "advisory".to_string(),
+        name: "Advisory Board".to_string(),
+        roles: vec!["Chair".to_string(), "KOL".to_string(), "Sponsor".to_string()],
+        created_at: String::new(),
+        updated_at: String::new(),
+    }];
+
+    let mut opts = options();
+    opts.speaker_label_mode = SpeakerLabelMode::RoleSet;
+    opts.role_set_id = Some("advisory".to_string());
+
+    // The mode needs its roles looked up before it can be submitted
+    let err = provider.submit("https://cdn.example/audio", &opts).await.unwrap_err();
+    assert!(matches!(err, TranscribeError::InvalidOptions(_)));
+
+    let resolved = resolve_role_set(&role_sets, &opts).unwrap();
+    provider.submit("https://cdn.example/audio", &resolved).await.unwrap();
+    let submitted = server.requests_to("POST", "/v2/transcript")[0].json();
+    let identification = &submitted["speech_understanding"]["request"]["speaker_identification"];
+    assert_eq!(identification["speaker_type"], "role");
+    assert_eq!(identification["known_values"], serde_json::json!(["Chair", "KOL", "Sponsor"]));
+
+    opts.role_set_id = Some("deleted".to_string());
+    assert!(matches!(resolve_role_set(&role_sets, &opts), Err(RoleSetError::NotFound(_))));
+}
+
+#[test]
+fn unknown_speaker_label_mode_is_rejected() {
+    let mut value = serde_json::to_value(options()).unwrap();
+    assert_eq!(value["speaker_label_mode"], "interview");
+
+    value["speaker_label_mode"] = serde_json::json!("roundtable");
+    let err = serde_json::from_value::<TranscriptionOptions>(value).unwrap_err();
+    assert!(err.to_string().contains("unknown variant `roundtable`"));
+}
@@ -0,0 +1,137 @@
+// Vocabulary extraction (OpenAI) and speaker identification (LeMUR) against the local mock server
+
+mod common;
+
+use common::{MockServer, MOCK_API_KEY};
+use ohg_scribe_lib::commands::lemur::{request_speaker_names, LEMUR_RETRY};
+use ohg_scribe_lib::commands::settings::ApiEndpoints;
+use ohg_scribe_lib::commands::vocabulary_extract::{request_vocabulary_terms, EXTRACTION_RETRY};
+use ohg_scribe_lib::http::RetryPolicy;
+use std::time::Duration;
+
+// Retry quickly so failure cases don't slow the suite down
+const FAST_RETRY: RetryPolicy = RetryPolicy::new(3, Duration::from_millis(10), Duration::from_millis(50));
+
+fn endpoints_for(server: &MockServer) -> ApiEndpoints {
+    ApiEndpoints {
+        assemblyai: server.base_url(),
+        openai: server.base_url(),
+    }
+}
+
+#[tokio::test]
+async fn extracts_terms_from_document_text() {
+    let server = MockServer::start().await;
+    let endpoints = endpoints_for(&server);
+
+    let document = "Semaglutide (Ozempic) is a GLP-1 receptor agonist that lowers HbA1c.";
+    let extracted = request_vocabulary_terms(&reqwest::Client::new(), &endpoints.openai_v1(), &FAST_RETRY, document.to_string(), MOCK_API_KEY)
+        .await
+        .unwrap();
+
+    assert_eq!(extracted.suggested_name, "Semaglutide Advisory Board");
+    assert_eq!(extracted.categories.len(), 2);
+    assert_eq!(extracted.categories[0].terms, vec!["semaglutide", "Ozempic"]);
+
+    let request = server.requests_to("POST", "/v1/chat/completions")[0].clone();
+    assert_eq!(request.headers["authorization"], format!("Bearer {}", MOCK_API_KEY));
+    let body = request.json();
+    assert_eq!(body["model"], "gpt-4o-mini");
+    assert!(body["messages"][1]["content"].as_str().unwrap().contains(document));
+}
+
+#[tokio::test]
+async fn extraction_surfaces_api_errors() {
+    let server = MockServer::start().await;
+    let endpoints = endpoints_for(&server);
+
+    let err = request_vocabulary_terms(&reqwest::Client::new(), &endpoints.openai_v1(), &FAST_RETRY, "text".to_string(), "wrong-key")
+        .await
+        .unwrap_err();
+
+    assert!(err.contains("401"), "unexpected error: {}", err);
+}
+
+#[tokio::test]
+async fn identifies_named_speakers_and_skips_unknown() {
+    let server = MockServer::start().await;
+    let endpoints = endpoints_for(&server);
+
+    let mapping = request_speaker_names(
+        &reqwest::Client::new(),
+        &endpoints.lemur_v3(),
+        &FAST_RETRY,
+        "A: Hi, I'm Alice Smith.\nB: Thanks for having me.".to_string(),
+        vec!["A".to_string(), "B".to_string()],
+        MOCK_API_KEY,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(mapping.len(), 1);
+    assert_eq!(mapping["A"], "Alice Smith");
+    assert_eq!(server.requests_to("POST", "/lemur/v3/generate/question-answer").len(), 1);
+}
+
+#[tokio::test]
+async fn extraction_retries_rate_limits() {
+    let server = MockServer::start().await;
+    let endpoints = endpoints_for(&server);
+    server.fail_next("POST", "/v1/chat/completions", "429 Too Many Requests", Some("0"), 2);
+
+    let extracted = request_vocabulary_terms(&reqwest::Client::new(), &endpoints.openai_v1(), &FAST_RETRY, "text".to_string(), MOCK_API_KEY)
+        .await
+        .unwrap();
+
+    assert_eq!(extracted.categories.len(), 2);
+    assert_eq!(server.requests_to("POST", "/v1/chat/completions").len(), 3);
+}
+
+#[tokio::test]
+async fn lemur_gives_up_after_its_retry_budget() {
+    let server = MockServer::start().await;
+    let endpoints = endpoints_for(&server);
+    server.fail_next("POST", "/lemur/v3/generate/question-answer", "503 Service Unavailable", None, 10);
+
+    let err = request_speaker_names(
+        &reqwest::Client::new(),
+        &endpoints.lemur_v3(),
+        &FAST_RETRY,
+        "A: Hello.".to_string(),
+        vec!["A".to_string()],
+        MOCK_API_KEY,
+    )
+    .await
+    .unwrap_err();
+
+    assert!(err.to_string().contains("after 3 attempt(s)"), "unexpected error: {}", err);
+    assert_eq!(server.requests_to("POST", "/lemur/v3/generate/question-answer").len(), 3);
+}
+
+#[tokio::test]
+async fn billed_requests_are_not_repeated_after_a_timeout() {
+    let server = MockServer::start().await;
+    let endpoints = endpoints_for(&server);
+    server.delay_next("POST", "/v1/chat/completions", Duration::from_secs(2), 1);
+    server.delay_next("POST", "/lemur/v3/generate/question-answer", Duration::from_secs(2), 1);
+    let client = reqwest::Client::builder().timeout(Duration::from_millis(200)).build().unwrap();
+
+    let err = request_vocabulary_terms(&client, &endpoints.openai_v1(), &EXTRACTION_RETRY, "text".to_string(), MOCK_API_KEY)
+        .await
+        .unwrap_err();
+    assert!(err.contains("after 1 attempt(s)"), "unexpected error: {}", err);
+    assert_eq!(server.requests_to("POST", "/v1/chat/completions").len(), 1);
+
+    let err = request_speaker_names(
+        &client,
+        &endpoints.lemur_v3(),
+        &LEMUR_RETRY,
+        "A: Hello.".to_string(),
+        vec!["A".to_string()],
+        MOCK_API_KEY,
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("after 1 attempt(s)"), "unexpected error: {}", err);
+    assert_eq!(server.requests_to("POST", "/lemur/v3/generate/question-answer").len(), 1);
+}
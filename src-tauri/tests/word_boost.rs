@@ -0,0 +1,74 @@
+// Ranking of word_boost terms when the merged vocabularies exceed the limit
+
+use ohg_scribe_lib::commands::word_boost::{
+    select_word_boost, BoostCandidate, DropReason, TermStats, WordBoostSelection, WordBoostStats,
+};
+
+fn candidate(term: &str, priority: i32, source: &str) -> BoostCandidate {
+    BoostCandidate {
+        term: term.to_string(),
+        priority,
+        source: source.to_string(),
+    }
+}
+
+fn reason_for(selection: &WordBoostSelection, term: &str) -> Option<DropReason> {
+    selection.dropped.iter().find(|d| d.term == term).map(|d| d.reason)
+}
+
+#[test]
+fn duplicates_keep_the_highest_priority_copy() {
+    let selection = select_word_boost(
+        vec![
+            candidate("glp-1", 0, "Diabetes"),
+            candidate("GLP-1", 10, "Obesity"),
+            candidate("  semaglutide ", 0, "Diabetes"),
+            candidate("Semaglutide", 0, "Obesity"),
+        ],
+        &WordBoostStats::default(),
+        200,
+    );
+
+    assert_eq!(selection.selected, vec!["GLP-1", "semaglutide"]);
+    assert_eq!(selection.dropped.len(), 2);
+    assert!(selection.dropped.iter().all(|d| d.reason == DropReason::Duplicate));
+    assert_eq!(selection.dropped[0].source, "Diabetes");
+}
+
+#[test]
+fn terms_over_six_words_are_rejected() {
+    let selection = select_word_boost(
+        vec![
+            candidate("glucagon like peptide one receptor agonist", 0, "custom"),
+            candidate("one two three four five six seven", 0, "custom"),
+        ],
+        &WordBoostStats::default(),
+        200,
+    );
+
+    assert_eq!(selection.selected, vec!["glucagon like peptide one receptor agonist"]);
+    assert_eq!(reason_for(&selection, "one two three four five six seven"), Some(DropReason::TooLong));
+}
+
+#[test]
+fn priority_then_hit_rate_decide_what_fits() {
+    let mut stats = WordBoostStats::default();
+    stats.terms.insert("ozempic".to_string(), TermStats { boosted: 10, hits: 9 });
+    stats.terms.insert("wegovy".to_string(), TermStats { boosted: 10, hits: 0 });
+
+    let selection = select_word_boost(
+        vec![
+            candidate("Wegovy", 0, "Obesity"),
+            candidate("Mounjaro", 0, "Obesity"),
+            candidate("Ozempic", 0, "Obesity"),
+            candidate("HbA1c", 5, "Diabetes"),
+        ],
+        &stats,
+        3,
+    );
+
+    // Higher priority first, then frequently-heard terms, then unseen ones; the
+    // rarely-heard term is the one cut
+    assert_eq!(selection.selected, vec!["HbA1c", "Ozempic", "Mounjaro"]);
+    assert_eq!(reason_for(&selection, "Wegovy"), Some(DropReason::OverLimit));
+}
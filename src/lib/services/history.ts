@@ -2,7 +2,7 @@
 // Service for storing and retrieving transcription history
 
 import { invoke } from '@tauri-apps/api/core';
-import type { TranscriptResult } from '../types';
+import type { CleanupProfile, SessionFile, TimelineMap, TranscriptResult } from '../types';
 
 export interface HistoryEntry {
     id: string;
@@ -14,6 +14,9 @@ export interface HistoryEntry {
     wordCount: number;
     transcript: TranscriptResult;
     audioPath?: string; // Path to stored audio file for playback
+    timeline?: TimelineMap; // Set when only parts of the file were transcribed; maps audio time to source time
+    cleanupProfile?: CleanupProfile; // Audio cleanup applied before transcription
+    sessionFiles?: SessionFile[]; // Recordings joined into this transcript, in order
     options: {
         speakerNames: string[];
         includedSummary: boolean;
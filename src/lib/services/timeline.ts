@@ -0,0 +1,63 @@
+// src/lib/services/timeline.ts
+// Time ranges to transcribe, and mapping between trimmed audio and the source recording
+
+import type { TimelineMap, TimeRange } from '../types';
+
+// Source time (ms) of a time in the converted audio
+export function toSourceTime(timeline: TimelineMap | null | undefined, audioMs: number): number {
+    const segments = timeline?.segments ?? [];
+    if (segments.length === 0) return audioMs;
+
+    const segment =
+        segments.find((s) => audioMs < s.output_start_ms + s.duration_ms) ??
+        segments[segments.length - 1];
+    return segment.source_start_ms + (audioMs - segment.output_start_ms);
+}
+
+// Time (ms) in the converted audio of a source time; times in a cut part snap to the next kept one
+export function toAudioTime(timeline: TimelineMap | null | undefined, sourceMs: number): number {
+    const segments = timeline?.segments ?? [];
+    if (segments.length === 0) return sourceMs;
+
+    for (const s of segments) {
+        if (sourceMs < s.source_start_ms) return s.output_start_ms;
+        if (sourceMs < s.source_start_ms + s.duration_ms) {
+            return s.output_start_ms + (sourceMs - s.source_start_ms);
+        }
+    }
+    const last = segments[segments.length - 1];
+    return last.output_start_ms + last.duration_ms;
+}
+
+// "1:05:30" / "12:00" / "90" -> ms
+function parseClock(text: string): number | null {
+    const parts = text.trim().split(':');
+    if (parts.length > 3 || parts.some((p) => !/^\d+(\.\d+)?$/.test(p))) return null;
+    return Math.round(parts.reduce((total, p) => total * 60 + Number(p), 0) * 1000);
+}
+
+function formatClock(ms: number): string {
+    const total = Math.round(ms / 1000);
+    const h = Math.floor(total / 3600);
+    const m = Math.floor((total % 3600) / 60);
+    const s = String(total % 60).padStart(2, '0');
+    return h > 0 ? `${h}:${String(m).padStart(2, '0')}:${s}` : `${m}:${s}`;
+}
+
+// Parse "12:00-47:00, 1:05:00-1:10:00" into ranges. Returns null if any part is invalid.
+export function parseRanges(text: string): TimeRange[] | null {
+    const ranges: TimeRange[] = [];
+    for (const part of text.split(',').filter((p) => p.trim())) {
+        const [start, end, ...rest] = part.split('-');
+        if (end === undefined || rest.length > 0) return null;
+        const start_ms = parseClock(start);
+        const end_ms = parseClock(end);
+        if (start_ms === null || end_ms === null || end_ms <= start_ms) return null;
+        ranges.push({ start_ms, end_ms });
+    }
+    return ranges;
+}
+
+export function formatRanges(ranges: TimeRange[]): string {
+    return ranges.map((r) => `${formatClock(r.start_ms)}-${formatClock(r.end_ms)}`).join(', ');
+}
@@ -1,10 +1,46 @@
 // src/lib/services/transcription.ts
 import { invoke } from '@tauri-apps/api/core';
-import type { TranscriptionOptions, TranscriptResult, TranscriptSegment } from '../types';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { AudioSelection, BoostParam, CleanupProfile, RoleSet, SessionFile, SpeakerLabelMode, TimelineMap, TimeRange, TranscriptionOptions, TranscriptResult, TranscriptSegment, TranscriptWord } from '../types';
+import { vocabularyStore } from '../stores/vocabulary';
 
 export interface ConversionResult {
     output_path: string;
     temp_dir: string;
+    chunks: AudioChunk[];  // Empty unless splitting was requested and needed
+    timeline: TimelineMap | null;  // Set when only some ranges were converted
+    files: SessionFile[];  // Set when several files were joined into a session
+}
+
+// One piece of a recording split at silence points
+export interface AudioChunk {
+    path: string;
+    offset_ms: number;
+    duration_ms: number;  // Includes the overlap into the next chunk
+}
+
+// Leave fields out to use the backend defaults (-40 dB, cut silences of 10 s or more, keep 1 s each side)
+export interface SilenceOptions {
+    threshold_db?: number;
+    min_silence_secs?: number;
+    padding_secs?: number;
+}
+
+// What to convert and how; leave fields out for the main audio of the whole file, unfiltered
+export interface ConvertOptions {
+    audio?: AudioSelection | null;
+    ranges?: TimeRange[];
+    cleanup?: CleanupProfile;
+    strip_silence?: SilenceOptions | null;
+}
+
+// Leave fields out to use the backend defaults (30 min chunks, 40 min max, 20 s overlap)
+export interface SplitOptions {
+    target_chunk_secs?: number;
+    max_chunk_secs?: number;
+    overlap_secs?: number;
+    silence_threshold_db?: number;
+    min_silence_secs?: number;
 }
 
 export interface RustTranscriptionOptions {
@@ -14,8 +50,20 @@ export interface RustTranscriptionOptions {
     detect_topics: boolean;
     analyze_sentiment: boolean;
     extract_key_phrases: boolean;  // auto_highlights in AssemblyAI
-    speaker_label_mode: string;    // 'generic' | 'auto-names' | 'known-names' | 'interview' | etc.
+    speaker_label_mode: SpeakerLabelMode;
     speaker_values: string[];      // Names or custom roles from user input
+    role_set_id: string | null;    // Resolved to its roles by the backend
+    language_code: string | null;  // null = AssemblyAI default
+    language_detection: boolean;
+    redact_pii: boolean;
+    redact_pii_policies: string[];
+    auto_chapters: boolean;
+    entity_detection: boolean;
+    filter_profanity: boolean;
+    disfluencies: boolean;
+    boost_param: BoostParam | null;
+    custom_spelling: { from: string[]; to: string }[];  // From the selected vocabularies
+    vocabulary_ids: string[];  // Selected vocabularies; the backend ranks their terms into word_boost
 }
 
 export interface TranscriptResponse {
@@ -31,14 +79,38 @@ export interface TranscriptResponse {
     auto_highlights_result?: {
         results: { text: string; count: number; rank: number }[];
     };
+    language_code?: string;
+    language_confidence?: number;
+    chapters?: Chapter[];
+    entities?: Entity[];
+    chunk_transcript_ids?: string[];  // Set when the transcript was merged from chunks
+    track_transcript_ids?: string[];  // Set when the transcript was interleaved from speaker tracks
     error?: string;
 }
 
+export interface Chapter {
+    headline: string;
+    gist: string;
+    summary: string;
+    start: number;
+    end: number;
+}
+
+export interface Entity {
+    entity_type: string;
+    text: string;
+    start: number;
+    end: number;
+}
+
 export interface Utterance {
     speaker: string;
     text: string;
     start: number;
     end: number;
+    confidence?: number;
+    words?: TranscriptWord[];
+    source?: { file: number; local_ms: number };  // Set for joined sessions
 }
 
 export interface SentimentResult {
@@ -72,9 +144,261 @@ export async function setOpenAIKey(apiKey: string): Promise<void> {
     return await invoke('set_openai_key', { apiKey });
 }
 
+// API base URLs (defaults to the public AssemblyAI / OpenAI APIs)
+export interface ApiEndpoints {
+    assemblyai: string;
+    openai: string;
+}
+
+export async function getApiEndpoints(): Promise<ApiEndpoints> {
+    return await invoke<ApiEndpoints>('get_api_endpoints');
+}
+
+export async function setApiEndpoints(assemblyai: string | null, openai: string | null): Promise<ApiEndpoints> {
+    return await invoke<ApiEndpoints>('set_api_endpoints', { assemblyai, openai });
+}
+
+// Outbound network (proxy, extra root CAs, timeouts) for the backend HTTP client
+export interface NetworkSettings {
+    proxy_url: string | null;
+    proxy_username: string | null;
+    proxy_password: string | null;
+    ca_certificates: string[];
+    connect_timeout_secs: number | null;
+    read_timeout_secs: number | null;
+}
+
+export async function getNetworkSettings(): Promise<NetworkSettings> {
+    return await invoke<NetworkSettings>('get_network_settings');
+}
+
+export async function setNetworkSettings(network: NetworkSettings): Promise<NetworkSettings> {
+    return await invoke<NetworkSettings>('set_network_settings', { network });
+}
+
+// Data retention: removing transcripts (and their uploaded audio) from AssemblyAI
+export interface RemoteDeletion {
+    transcript_id: string;
+    provider: string;
+    history_id: string | null;
+    deleted_at: string;
+}
+
+export async function deleteRemoteTranscript(
+    transcriptId: string,
+    apiKey: string,
+    historyId?: string
+): Promise<RemoteDeletion> {
+    return await invoke<RemoteDeletion>('delete_remote_transcript', { transcriptId, apiKey, historyId });
+}
+
+export async function listRemoteDeletions(): Promise<RemoteDeletion[]> {
+    return await invoke<RemoteDeletion[]>('list_remote_deletions');
+}
+
+export async function getAutoDeleteRemote(): Promise<boolean> {
+    return await invoke<boolean>('get_auto_delete_remote');
+}
+
+export async function setAutoDeleteRemote(enabled: boolean): Promise<void> {
+    return await invoke('set_auto_delete_remote', { enabled });
+}
+
+// Transcribe the chunks of a split recording in parallel and merge them into one transcript.
+// The AssemblyAI transcript of each chunk is listed in chunk_transcript_ids.
+export async function transcribeChunks(
+    chunks: AudioChunk[],
+    apiKey: string,
+    options: TranscriptionOptions,
+    jobId: string
+): Promise<TranscriptResponse> {
+    return await invoke<TranscriptResponse>('transcribe_chunks', {
+        chunks,
+        apiKey,
+        options: toRustOptions(options),
+        jobId
+    });
+}
+
+// One participant's converted audio; everything on it is credited to `name`
+export interface SpeakerTrack {
+    name: string;
+    path: string;
+    timeline?: TimelineMap | null;
+}
+
+// Transcribe one track per participant in parallel and interleave them into one transcript.
+// The AssemblyAI transcript of each track is listed in track_transcript_ids.
+export async function transcribeTracks(
+    tracks: SpeakerTrack[],
+    apiKey: string,
+    options: TranscriptionOptions,
+    jobId: string
+): Promise<TranscriptResponse> {
+    return await invoke<TranscriptResponse>('transcribe_tracks', {
+        tracks,
+        apiKey,
+        options: toRustOptions(options),
+        jobId
+    });
+}
+
+// Transcribe on this machine with whisper.cpp (no upload); returns the same shape as AssemblyAI
+export async function transcribeLocally(inputPath: string, languageCode?: string): Promise<TranscriptResponse> {
+    return await invoke<TranscriptResponse>('transcribe_locally', { inputPath, languageCode: languageCode || null });
+}
+
+export async function getWhisperModelPath(): Promise<string | null> {
+    return await invoke<string | null>('get_whisper_model_path');
+}
+
+// Pass null to clear the model
+export async function setWhisperModelPath(path: string | null): Promise<void> {
+    await invoke('set_whisper_model_path', { path });
+}
+
+export async function listRoleSets(): Promise<RoleSet[]> {
+    return await invoke<RoleSet[]>('list_role_sets');
+}
+
+// Creates a role set, or updates it when id is given
+export async function saveRoleSet(name: string, roles: string[], id?: string): Promise<RoleSet> {
+    return await invoke<RoleSet>('save_role_set', { id: id ?? null, name, roles });
+}
+
+export async function deleteRoleSet(id: string): Promise<void> {
+    await invoke('delete_role_set', { id });
+}
+
+export interface LowConfidenceSpan {
+    speaker: string;
+    start: number;
+    end: number;
+    text: string;
+    confidence: number;  // Lowest word confidence in the span
+    word_count: number;
+}
+
+// Passages of a saved transcript below the confidence threshold (backend default 0.7)
+export async function getLowConfidenceSpans(historyId: string, threshold?: number): Promise<LowConfidenceSpan[]> {
+    return await invoke<LowConfidenceSpan[]>('get_low_confidence_spans', { historyId, threshold });
+}
+
+// Word boost ranking: which terms fit under the limit, and what was dropped and why
+export interface WordBoostSelection {
+    selected: string[];
+    dropped: { term: string; source: string; reason: 'duplicate' | 'too_long' | 'over_limit' }[];
+}
+
+export async function previewWordBoost(options: TranscriptionOptions): Promise<WordBoostSelection> {
+    return await invoke<WordBoostSelection>('preview_word_boost', { options: toRustOptions(options) });
+}
+
+export async function getWordBoostStats(): Promise<Record<string, { boosted: number; hits: number }>> {
+    return await invoke('get_word_boost_stats');
+}
+
+// Media inspection
+export interface MediaStream {
+    index: number;  // As in FFmpeg's `-map 0:<index>`
+    kind: 'audio' | 'video' | 'subtitle' | 'data' | 'attachment';
+    codec: string;
+    language: string | null;
+    title: string | null;
+    default: boolean;
+    sample_rate: number | null;  // Audio only
+    channels: number | null;
+    channel_layout: string | null;
+    width: number | null;  // Video only
+    height: number | null;
+}
+
+export interface MediaInfo {
+    container: string;
+    duration_ms: number | null;
+    bitrate_kbps: number | null;
+    streams: MediaStream[];
+}
+
+export async function inspectMedia(inputPath: string): Promise<MediaInfo> {
+    return await invoke<MediaInfo>('inspect_media', { inputPath });
+}
+
 // FFmpeg conversion
-export async function convertToAudio(inputPath: string): Promise<ConversionResult> {
-    return await invoke<ConversionResult>('convert_to_audio', { inputPath });
+// Pass a `jobId` to receive `conversion-progress` events for it
+export async function convertToAudio(
+    inputPath: string,
+    split?: SplitOptions,
+    jobId?: string,
+    options?: ConvertOptions
+): Promise<ConversionResult> {
+    return await invoke<ConversionResult>('convert_to_audio', {
+        inputPath,
+        split: split ?? null,
+        jobId: jobId ?? null,
+        options: options ?? null,
+    });
+}
+
+// Join several recordings of one meeting, in order, into one converted file
+export async function convertSession(
+    inputPaths: string[],
+    split?: SplitOptions,
+    jobId?: string,
+    options?: ConvertOptions
+): Promise<ConversionResult> {
+    return await invoke<ConversionResult>('convert_session', {
+        inputPaths,
+        split: split ?? null,
+        jobId: jobId ?? null,
+        options: options ?? null,
+    });
+}
+
+// Mix per-participant tracks into one file for playback
+export async function convertMixdown(inputPaths: string[], jobId?: string): Promise<ConversionResult> {
+    return await invoke<ConversionResult>('convert_mixdown', {
+        inputPaths,
+        jobId: jobId ?? null,
+    });
+}
+
+// Mark each utterance of a session transcript with the file it came from
+export async function locateSessionSources(
+    response: TranscriptResponse,
+    files: SessionFile[]
+): Promise<TranscriptResponse> {
+    return await invoke<TranscriptResponse>('locate_session_sources', { response, files });
+}
+
+export interface CleanupProfileInfo {
+    id: CleanupProfile;
+    label: string;
+    filters: string[];  // FFmpeg audio filters, in order
+}
+
+export async function listCleanupProfiles(): Promise<CleanupProfileInfo[]> {
+    return await invoke<CleanupProfileInfo[]>('list_cleanup_profiles');
+}
+
+// Move a transcript of trimmed audio onto the source recording's timeline
+export async function remapTranscript(
+    response: TranscriptResponse,
+    timeline: TimelineMap
+): Promise<TranscriptResponse> {
+    return await invoke<TranscriptResponse>('remap_transcript', { response, timeline });
+}
+
+export interface ConversionProgress {
+    job_id: string;
+    percent: number | null;  // Null until FFmpeg has reported the input duration
+    processed_ms: number;
+    duration_ms: number | null;
+    eta_secs: number | null;
+}
+
+export async function onConversionProgress(handler: (progress: ConversionProgress) => void): Promise<UnlistenFn> {
+    return await listen<ConversionProgress>('conversion-progress', (event) => handler(event.payload));
 }
 
 export async function cleanupTempDir(tempDir: string): Promise<void> {
@@ -82,21 +406,31 @@ export async function cleanupTempDir(tempDir: string): Promise<void> {
 }
 
 // AssemblyAI API
-export async function uploadAudio(filePath: string, apiKey: string): Promise<string> {
-    return await invoke<string>('upload_audio', { filePath, apiKey });
+export interface UploadProgress {
+    upload_id: string;
+    bytes_sent: number;
+    total_bytes: number;
 }
 
-export async function submitTranscription(
-    uploadUrl: string,
-    apiKey: string,
-    options: TranscriptionOptions
-): Promise<string> {
+export async function uploadAudio(filePath: string, apiKey: string, uploadId?: string): Promise<string> {
+    return await invoke<string>('upload_audio', { filePath, apiKey, uploadId });
+}
+
+export async function cancelUpload(uploadId: string): Promise<boolean> {
+    return await invoke<boolean>('cancel_upload', { uploadId });
+}
+
+export async function onUploadProgress(handler: (progress: UploadProgress) => void): Promise<UnlistenFn> {
+    return await listen<UploadProgress>('upload-progress', (event) => handler(event.payload));
+}
+
+function toRustOptions(options: TranscriptionOptions): RustTranscriptionOptions {
     // Parse speaker values from comma-separated input
     const speakerValues = options.speakerNamesInput
         ? options.speakerNamesInput.split(',').map(s => s.trim()).filter(s => s.length > 0)
         : [];
 
-    const rustOptions: RustTranscriptionOptions = {
+    return {
         max_speakers: options.speakerCount === 'auto' ? null : options.speakerCount,
         boost_words: options.boostWords,
         include_summary: options.includeSummary,
@@ -104,9 +438,28 @@ export async function submitTranscription(
         analyze_sentiment: options.analyzeSentiment,
         extract_key_phrases: options.extractKeyPhrases,
         speaker_label_mode: options.speakerLabelMode,
-        speaker_values: speakerValues
+        speaker_values: speakerValues,
+        role_set_id: options.speakerLabelMode === 'role-set' ? options.roleSetId ?? null : null,
+        language_code: options.languageDetection ? null : options.languageCode || null,
+        language_detection: options.languageDetection ?? false,
+        redact_pii: options.redactPii ?? false,
+        redact_pii_policies: options.redactPiiPolicies ?? [],
+        auto_chapters: options.autoChapters ?? false,
+        entity_detection: options.entityDetection ?? false,
+        filter_profanity: options.filterProfanity ?? false,
+        disfluencies: options.disfluencies ?? false,
+        boost_param: options.boostParam ?? null,
+        custom_spelling: vocabularyStore.getSpellingsForPresets(options.selectedPresets ?? []),
+        vocabulary_ids: options.selectedPresets ?? []
     };
-    return await invoke<string>('submit_transcription', { uploadUrl, apiKey, options: rustOptions });
+}
+
+export async function submitTranscription(
+    uploadUrl: string,
+    apiKey: string,
+    options: TranscriptionOptions
+): Promise<string> {
+    return await invoke<string>('submit_transcription', { uploadUrl, apiKey, options: toRustOptions(options) });
 }
 
 export async function pollTranscription(
@@ -116,38 +469,103 @@ export async function pollTranscription(
     return await invoke<TranscriptResponse>('poll_transcription', { transcriptId, apiKey });
 }
 
+// Backend transcription jobs (convert → upload → submit → poll → save, all in Rust)
+export type JobStage = 'queued' | 'converting' | 'uploading' | 'transcribing' | 'saving' | 'complete' | 'error';
+
+export interface TranscriptionJobEvent {
+    job_id: string;
+    stage: JobStage;
+    progress: number;
+    history_id?: string;
+    error?: string;
+}
+
+export async function startTranscriptionJob(
+    inputPath: string,
+    apiKey: string,
+    options: TranscriptionOptions,
+    convert?: ConvertOptions
+): Promise<string> {
+    return await invoke<string>('start_transcription_job', {
+        inputPath,
+        apiKey,
+        options: toRustOptions(options),
+        convert: convert ?? null,
+    });
+}
+
+export async function getTranscriptionJob(jobId: string): Promise<TranscriptionJobEvent | null> {
+    return await invoke<TranscriptionJobEvent | null>('get_transcription_job', { jobId });
+}
+
+// Jobs persisted on disk (unfinished jobs resume automatically at startup)
+export interface PersistedTranscriptionJob {
+    job_id: string;
+    input_path: string;
+    stage: JobStage;
+    options: RustTranscriptionOptions;
+    upload_url?: string;
+    transcript_id?: string;
+    error?: string;
+    created_at: string;
+    updated_at: string;
+}
+
+export async function listTranscriptionJobs(): Promise<PersistedTranscriptionJob[]> {
+    return await invoke<PersistedTranscriptionJob[]>('list_transcription_jobs');
+}
+
+// Only failed jobs can be dismissed; running ones are refused
+export async function dismissTranscriptionJob(jobId: string): Promise<void> {
+    return await invoke('dismiss_transcription_job', { jobId });
+}
+
+export async function onTranscriptionJobEvent(
+    handler: (event: TranscriptionJobEvent) => void
+): Promise<UnlistenFn> {
+    return await listen<TranscriptionJobEvent>('transcription-job', (event) => handler(event.payload));
+}
+
 // Poll until transcription is complete
+export interface TranscriptStatusEvent {
+    job_id: string;
+    transcript_id: string;
+    status: TranscriptResponse['status'];
+}
+
+export async function onTranscriptStatus(handler: (event: TranscriptStatusEvent) => void): Promise<UnlistenFn> {
+    return await listen<TranscriptStatusEvent>('transcript-status', (event) => handler(event.payload));
+}
+
+// Polling happens in the backend; status changes arrive as `transcript-status` events.
+// Pass a `jobId` to be able to stop the wait with `cancelTranscriptionWait`.
 export async function waitForTranscription(
     transcriptId: string,
     apiKey: string,
     onProgress?: (status: string) => void,
-    initialDelayMs: number = 5000,
-    pollIntervalMs: number = 3000,
+    jobId: string = crypto.randomUUID(),
     timeoutMs: number = 30 * 60 * 1000 // 30 minutes
 ): Promise<TranscriptResponse> {
-    const startTime = Date.now();
-
-    // Initial delay
-    await new Promise(resolve => setTimeout(resolve, initialDelayMs));
-
-    while (Date.now() - startTime < timeoutMs) {
-        const response = await pollTranscription(transcriptId, apiKey);
-
-        onProgress?.(response.status);
-
-        if (response.status === 'completed') {
-            return response;
-        }
-
-        if (response.status === 'error') {
-            throw new Error(response.error || 'Transcription failed');
+    const unlisten = await onTranscriptStatus((event) => {
+        if (event.job_id === jobId) {
+            onProgress?.(event.status);
         }
+    });
 
-        // Wait before next poll
-        await new Promise(resolve => setTimeout(resolve, pollIntervalMs));
+    try {
+        return await invoke<TranscriptResponse>('wait_for_transcription', {
+            transcriptId,
+            apiKey,
+            jobId,
+            timeoutSecs: Math.ceil(timeoutMs / 1000),
+        });
+    } finally {
+        unlisten();
     }
+}
 
-    throw new Error('Transcription timed out');
+export async function cancelTranscriptionWait(jobId: string): Promise<boolean> {
+    return await invoke<boolean>('cancel_transcription_wait', { jobId });
 }
 
 // Convert raw API response to our segment format
@@ -188,7 +606,11 @@ export function parseTranscriptResponse(
                 text: utterance.text,
                 start: utterance.start,
                 end: utterance.end,
-                sentiment
+                sentiment,
+                // Kept so low-confidence passages can be reviewed later
+                confidence: utterance.confidence,
+                words: utterance.words?.map(({ text, start, end, confidence }) => ({ text, start, end, confidence })),
+                source: utterance.source && { file: utterance.source.file, start: utterance.source.local_ms }
             });
         }
     }
@@ -7,17 +7,42 @@ function createQueueStore() {
 
     return {
         subscribe,
-        addFiles: (files: { filename: string; filepath: string }[]) => {
-            update(queue => {
-                const newJobs: FileJob[] = files.map((file, index) => ({
-                    id: `${Date.now()}-${index}-${Math.random().toString(36).substr(2, 9)}`,
-                    filename: file.filename,
-                    filepath: file.filepath,
-                    status: 'queued',
-                    progress: 0
-                }));
-                return [...queue, ...newJobs];
-            });
+        addFiles: (files: { filename: string; filepath: string }[]): FileJob[] => {
+            const newJobs: FileJob[] = files.map((file, index) => ({
+                id: `${Date.now()}-${index}-${Math.random().toString(36).substr(2, 9)}`,
+                filename: file.filename,
+                filepath: file.filepath,
+                status: 'queued',
+                progress: 0
+            }));
+            update(queue => [...queue, ...newJobs]);
+            return newJobs;
+        },
+        // One job for several recordings of the same meeting, joined in the given order
+        addSession: (files: { filename: string; filepath: string }[]): FileJob => {
+            const job: FileJob = {
+                id: `${Date.now()}-session-${Math.random().toString(36).substr(2, 9)}`,
+                filename: `${files[0].filename} + ${files.length - 1} more`,
+                filepath: files[0].filepath,
+                sessionPaths: files.map(file => file.filepath),
+                status: 'queued',
+                progress: 0
+            };
+            update(queue => [...queue, job]);
+            return job;
+        },
+        // One job for recordings made side by side, one per participant, in the given order
+        addTracks: (files: { filename: string; filepath: string }[]): FileJob => {
+            const job: FileJob = {
+                id: `${Date.now()}-tracks-${Math.random().toString(36).substr(2, 9)}`,
+                filename: `${files[0].filename} + ${files.length - 1} more tracks`,
+                filepath: files[0].filepath,
+                trackPaths: files.map(file => file.filepath),
+                status: 'queued',
+                progress: 0
+            };
+            update(queue => [...queue, job]);
+            return job;
         },
         updateJob: (id: string, updates: Partial<FileJob>) => {
             update(queue =>
@@ -55,6 +55,23 @@ function createVocabularyStore() {
             return [...new Set(terms)]; // Deduplicate
         },
 
+        // Get custom spelling rules for selected preset IDs (variants merged per term)
+        getSpellingsForPresets(ids: string[]): { from: string[]; to: string }[] {
+            const state = get({ subscribe });
+            const merged = new Map<string, Set<string>>();
+
+            ids.forEach(id => {
+                const vocab = state.vocabularies.find(v => v.id === id);
+                for (const [term, variants] of Object.entries(vocab?.spellings ?? {})) {
+                    const existing = merged.get(term) ?? new Set<string>();
+                    variants.forEach(v => existing.add(v));
+                    merged.set(term, existing);
+                }
+            });
+
+            return [...merged].map(([to, from]) => ({ from: [...from], to }));
+        },
+
         // Search vocabularies by name
         search(query: string): Vocabulary[] {
             const state = get({ subscribe });
@@ -78,8 +95,14 @@ function createVocabularyStore() {
         },
 
         // Create a new vocabulary
-        async create(name: string, category: string, terms: string[]): Promise<Vocabulary> {
-            const newVocab = await invoke<Vocabulary>('create_vocabulary', { name, category, terms });
+        async create(
+            name: string,
+            category: string,
+            terms: string[],
+            spellings?: Record<string, string[]>,
+            priority?: number
+        ): Promise<Vocabulary> {
+            const newVocab = await invoke<Vocabulary>('create_vocabulary', { name, category, terms, spellings, priority });
             update(s => ({
                 ...s,
                 vocabularies: [...s.vocabularies, newVocab]
@@ -88,7 +111,16 @@ function createVocabularyStore() {
         },
 
         // Update an existing vocabulary
-        async updateVocab(id: string, updates: { name?: string; category?: string; terms?: string[] }): Promise<Vocabulary> {
+        async updateVocab(
+            id: string,
+            updates: {
+                name?: string;
+                category?: string;
+                terms?: string[];
+                spellings?: Record<string, string[]>;
+                priority?: number;
+            }
+        ): Promise<Vocabulary> {
             const updated = await invoke<Vocabulary>('update_vocabulary', { id, ...updates });
             update(s => ({
                 ...s,
@@ -1,6 +1,6 @@
 import { writable } from 'svelte/store';
 import { getHistoryEntry, updateHistoryEntry } from '$lib/services/history';
-import type { TranscriptResult } from '$lib/types';
+import type { SessionFile, TimelineMap, TranscriptResult } from '$lib/types';
 
 export type ViewMode = 'compact' | 'workspace';
 
@@ -15,6 +15,8 @@ export interface WorkspaceState {
     isLoading: boolean;
     activeAttributionIds?: number[];
     audioPath: string | null;
+    timeline: TimelineMap | null;  // Stored audio holds only these parts of the source
+    sessionFiles: SessionFile[];  // Recordings joined into the transcript, if several
 }
 
 function createWorkspaceStore() {
@@ -28,7 +30,9 @@ function createWorkspaceStore() {
         minutesContent: null,
         isLoading: false,
         activeAttributionIds: undefined,
-        audioPath: null
+        audioPath: null,
+        timeline: null,
+        sessionFiles: []
     });
 
     return {
@@ -54,7 +58,9 @@ function createWorkspaceStore() {
                         isMinutesGenerated: !!entry.minutes,
                         minutesContent: entry.minutes || null,
                         isLoading: false,
-                        audioPath: entry.audioPath || null
+                        audioPath: entry.audioPath || null,
+                        timeline: entry.timeline || null,
+                        sessionFiles: entry.sessionFiles || []
                     }));
                 }
             } catch (e) {
@@ -130,7 +136,9 @@ function createWorkspaceStore() {
             minutesContent: null,
             isLoading: false,
             activeAttributionIds: undefined,
-            audioPath: null
+            audioPath: null,
+            timeline: null,
+            sessionFiles: []
         })
     };
 }
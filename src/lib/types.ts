@@ -8,7 +8,8 @@ export type SpeakerLabelMode =
   | 'interview'       // Roles: Interviewer, Interviewee
   | 'podcast'         // Roles: Host, Guest
   | 'panel'           // Roles: Moderator, Panelist
-  | 'custom-roles';   // User provides custom roles
+  | 'custom-roles'    // User provides custom roles
+  | 'role-set';       // Roles from a saved role set (roleSetId)
 
 export const SPEAKER_LABEL_OPTIONS: Record<SpeakerLabelMode, { label: string; type: 'none' | 'name' | 'role'; values?: string[] }> = {
   'generic': { label: 'Generic (Speaker A, B, C...)', type: 'none' },
@@ -18,8 +19,21 @@ export const SPEAKER_LABEL_OPTIONS: Record<SpeakerLabelMode, { label: string; ty
   'podcast': { label: 'Podcast (Host/Guest)', type: 'role', values: ['Host', 'Guest'] },
   'panel': { label: 'Panel (Moderator/Panelist)', type: 'role', values: ['Moderator', 'Panelist'] },
   'custom-roles': { label: 'Custom roles...', type: 'role' },
+  'role-set': { label: 'Saved role set...', type: 'role' },
 };
 
+// 'local' runs whisper.cpp on this machine; nothing is uploaded
+export type TranscriptionEngine = 'assemblyai' | 'local';
+
+// A saved, named list of roles (e.g. "Advisory Board: Chair, KOL, Sponsor")
+export interface RoleSet {
+  id: string;
+  name: string;
+  roles: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
 // Legacy aliases for backwards compatibility
 export type ConversationType = 'none' | 'interview' | 'meeting' | 'panel';
 export const CONVERSATION_TYPE_LABELS: Record<ConversationType, string> = {
@@ -29,10 +43,16 @@ export const CONVERSATION_TYPE_LABELS: Record<ConversationType, string> = {
   'panel': 'Panel Discussion',
 };
 
+export type BoostParam = 'low' | 'default' | 'high';
+
 export interface TranscriptionOptions {
   speakerCount: 'auto' | number;  // 'auto' or 2-20
   speakerLabelMode: SpeakerLabelMode;  // New: how to label speakers
+  engine?: TranscriptionEngine;  // Defaults to 'assemblyai'
+  splitLongRecordings?: boolean;  // Split long recordings at silences and transcribe the chunks in parallel
+  skipSilences?: boolean;  // Cut long silences (breaks, waiting for people) out before transcribing
   speakerNamesInput: string;  // Comma-separated names or custom roles
+  roleSetId?: string;  // Saved role set used when speakerLabelMode is 'role-set'
   boostWords: string[];
   boostWordsInput: string;  // Comma-separated string for the new input field
   selectedPresets: string[];  // Array of vocabulary preset IDs
@@ -40,17 +60,72 @@ export interface TranscriptionOptions {
   detectTopics: boolean;
   analyzeSentiment: boolean;
   extractKeyPhrases: boolean;  // New: auto_highlights in AssemblyAI
+  languageCode?: string;  // e.g. 'en_us', 'es'; unset = AssemblyAI default
+  languageDetection?: boolean;  // Detect the language instead of using languageCode
+  redactPii?: boolean;
+  redactPiiPolicies?: string[];  // e.g. 'person_name', 'phone_number'
+  autoChapters?: boolean;  // Can't be combined with includeSummary
+  entityDetection?: boolean;
+  filterProfanity?: boolean;
+  disfluencies?: boolean;  // Keep filler words like "um" and "uh"
+  boostParam?: BoostParam;  // How strongly to weight boostWords
   // Legacy fields for backwards compatibility
   speakerNames?: string[];
   conversationType?: ConversationType;
 }
 
+// Which audio to convert from a multi-track recording (stream indices from inspectMedia)
+export interface AudioSelection {
+  streams: number[];  // Empty = the file's default audio stream
+  channel: number | null;  // Keep one channel (0-based) of a single stream
+}
+
+// A stretch of the source recording to transcribe
+export interface TimeRange {
+  start_ms: number;
+  end_ms: number;
+}
+
+// Which parts of the source the converted audio holds; empty = all of it
+export interface TimelineMap {
+  segments: {
+    output_start_ms: number;  // Where the part starts in the converted audio
+    source_start_ms: number;  // Where it starts in the source
+    duration_ms: number;
+  }[];
+}
+
+// Filter chain run on the audio while converting, e.g. to lift a quiet far-end speaker
+export type CleanupProfile = 'none' | 'normalize' | 'denoise' | 'conference-room';
+
+export interface AudioTrack {
+  index: number;
+  language: string | null;
+  title: string | null;
+  channels: number | null;
+}
+
+// A stream holding one participant per channel (e.g. a podcast recorder's stereo mix)
+export interface SpeakerChannels {
+  stream: number;
+  channels: number;
+}
+
 export interface FileJob {
   id: string;
   filename: string;
   filepath: string;
   status: 'queued' | 'converting' | 'uploading' | 'transcribing' | 'generating' | 'complete' | 'error';
   progress: number;
+  durationMs?: number;  // From inspecting the file before conversion
+  audioTracks?: AudioTrack[];  // Audio streams found when inspecting
+  audioSelection?: AudioSelection;  // Unset = default stream, downmixed
+  ranges?: TimeRange[];  // Only transcribe these parts; unset = the whole file
+  cleanupProfile?: CleanupProfile;  // Unset = 'none'
+  sessionPaths?: string[];  // Several recordings of one meeting, joined in this order
+  trackPaths?: string[];  // One recording per participant, transcribed separately
+  speakerChannels?: SpeakerChannels;  // Each channel of this stream is one participant
+  etaSecs?: number;  // Estimated time left in the current step, when known
   error?: string;
   outputPath?: string;
   historyId?: string;  // ID of the saved history entry for navigating to transcript view
@@ -62,6 +137,23 @@ export interface TranscriptSegment {
   start: number;
   end: number;
   sentiment?: 'positive' | 'neutral' | 'negative';
+  confidence?: number;
+  words?: TranscriptWord[];
+  source?: { file: number; start: number };  // Joined sessions: file index and start (ms) within it
+}
+
+// One recording of a session joined from several files
+export interface SessionFile {
+  path: string;
+  offset_ms: number;  // Where the file starts in the session
+  duration_ms: number;
+}
+
+export interface TranscriptWord {
+  text: string;
+  start: number;
+  end: number;
+  confidence?: number;
 }
 
 export interface TranscriptResult {
@@ -5,6 +5,8 @@ export interface Vocabulary {
     name: string;
     category: string;
     terms: string[];
+    spellings?: Record<string, string[]>;  // term -> misheard variants to rewrite to it
+    priority?: number;  // Higher-priority vocabularies win word boost slots first
     isSystem: boolean;
     createdAt: string;
     updatedAt: string;